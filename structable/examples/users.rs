@@ -21,7 +21,7 @@ struct User {
 fn main() {
     let config = OutputConfig::default();
 
-    let users = vec![
+    let users = [
         User {
             id: 1,
             first_name: "Scooby".into(),
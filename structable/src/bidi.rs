@@ -0,0 +1,153 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Handling of the right-to-left text and truncation of the values
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Unicode First Strong Isolate
+const FSI: char = '\u{2068}';
+/// Unicode Pop Directional Isolate
+const PDI: char = '\u{2069}';
+
+/// Text direction of the column
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextDirection {
+    /// Determined by the content
+    #[default]
+    Auto,
+    /// Left-to-right
+    Ltr,
+    /// Right-to-left
+    Rtl,
+}
+
+/// Position of the ellipsis in the truncated values
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EllipsisPosition {
+    /// Keep the beginning of the value (`abcd…`)
+    #[default]
+    End,
+    /// Keep the end of the value (`…wxyz`), useful for paths and identifiers
+    Start,
+    /// Keep the beginning and the end of the value (`abc…xyz`)
+    Middle,
+}
+
+impl FromStr for EllipsisPosition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "end" => Ok(Self::End),
+            "start" => Ok(Self::Start),
+            "middle" => Ok(Self::Middle),
+            other => Err(format!("unknown ellipsis position `{other}`")),
+        }
+    }
+}
+
+/// Whether the character belongs to one of the right-to-left scripts
+fn is_rtl_char(c: char) -> bool {
+    matches!(c,
+        '\u{0590}'..='\u{08FF}'
+        | '\u{FB1D}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFF}'
+        | '\u{10800}'..='\u{10FFF}'
+        | '\u{1E800}'..='\u{1EFFF}')
+}
+
+/// Whether the text contains characters of the right-to-left scripts
+pub fn contains_rtl(text: &str) -> bool {
+    text.chars().any(is_rtl_char)
+}
+
+/// Truncate the value to the `width` characters replacing the omitted part with `…`
+///
+/// Truncated text containing right-to-left characters is wrapped into the directional isolate so
+/// that the ellipsis stays at its logical position and does not get reordered into the middle of
+/// the text by the terminal. Isolate marks are not counted into the width.
+pub(crate) fn truncate_value(value: String, width: usize, position: EllipsisPosition) -> String {
+    let count = value.chars().count();
+    if count <= width {
+        return value;
+    } else if width == 0 {
+        return String::new();
+    }
+    let keep = width - 1;
+    let (head, tail) = match position {
+        EllipsisPosition::End => (keep, 0),
+        EllipsisPosition::Start => (0, keep),
+        EllipsisPosition::Middle => (keep - keep / 2, keep / 2),
+    };
+    let mut res = String::with_capacity(value.len() + 8);
+    push_isolated(&mut res, value.chars().take(head));
+    res.push('…');
+    push_isolated(&mut res, value.chars().skip(count - tail));
+    res
+}
+
+/// Append the text wrapping it into the directional isolate when it contains right-to-left
+/// characters
+fn push_isolated<I: Iterator<Item = char>>(res: &mut String, chars: I) {
+    let text: String = chars.collect();
+    if contains_rtl(&text) {
+        res.push(FSI);
+        res.push_str(&text);
+        res.push(PDI);
+    } else {
+        res.push_str(&text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate() {
+        let end = EllipsisPosition::End;
+        assert_eq!(truncate_value("Scooby".into(), 4, end), "Sco…");
+        assert_eq!(truncate_value("Doo".into(), 4, end), "Doo");
+        assert_eq!(truncate_value("Doo".into(), 0, end), "");
+        assert_eq!(
+            truncate_value("שלום עולם".into(), 5, end),
+            "\u{2068}שלום\u{2069}…"
+        );
+        assert!(contains_rtl("abc مرحبا"));
+        assert!(!contains_rtl("abc"));
+    }
+
+    #[test]
+    fn test_truncate_position() {
+        let value = String::from("abcdefghijklmnopqrstuvwxyz");
+        assert_eq!(
+            truncate_value(value.clone(), 5, EllipsisPosition::Start),
+            "…wxyz"
+        );
+        assert_eq!(
+            truncate_value(value.clone(), 7, EllipsisPosition::Middle),
+            "abc…xyz"
+        );
+        assert_eq!(truncate_value(value, 6, EllipsisPosition::Middle), "abc…yz");
+        assert_eq!(
+            truncate_value("abc שלום".into(), 5, EllipsisPosition::Start),
+            "…\u{2068}שלום\u{2069}"
+        );
+        assert_eq!("middle".parse(), Ok(EllipsisPosition::Middle));
+    }
+}
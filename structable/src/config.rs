@@ -0,0 +1,194 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parsing of the [`OutputConfig`] from the compact string representation
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use crate::{OutputConfig, StructTableError};
+
+impl OutputConfig {
+    /// Parse set of fields from the comma separated string (i.e. `id,name`)
+    ///
+    /// Surrounding whitespaces and empty entries are ignored.
+    pub fn parse_fields<S: AsRef<str>>(fields: S) -> BTreeSet<String> {
+        fields
+            .as_ref()
+            .split(',')
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+            .map(String::from)
+            .collect()
+    }
+}
+
+/// Parse the configuration from the `;` separated options
+///
+/// Supported options are `fields=<comma separated titles or @groups>`,
+/// `extra_fields=<comma separated titles>`,
+/// `widths=<title>:<width>,...`,
+/// `max_rows=<N>`, `head=<N>`, `verbosity=<N>`, `tail=<N>`, `placeholder=<text>`,
+/// `ellipsis=<end|start|middle>`,
+/// `timezone=<original|utc|local|+HH:MM>` and the boolean flags `wide`, `pretty`,
+/// `hide_empty_columns`, `strict`, `raw`, `hyperlinks` (optionally followed by `=true` or
+/// `=false`). I.e.
+/// `fields=id,name;wide;pretty`.
+impl FromStr for OutputConfig {
+    type Err = StructTableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = OutputConfig::default();
+        for option in s.split(';').map(str::trim).filter(|x| !x.is_empty()) {
+            let (name, value) = match option.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim())),
+                None => (option, None),
+            };
+            match name {
+                "fields" => {
+                    // `@<tag>` entries select the groups of fields
+                    let (groups, fields) = Self::parse_fields(value.unwrap_or_default())
+                        .into_iter()
+                        .partition(|x| x.starts_with('@'));
+                    config.fields = fields;
+                    config.groups = groups
+                        .into_iter()
+                        .map(|x: String| x.trim_start_matches('@').to_string())
+                        .collect();
+                }
+                "extra_fields" => {
+                    config.extra_fields = Self::parse_fields(value.unwrap_or_default())
+                }
+                "widths" => {
+                    for width in value.unwrap_or_default().split(',').map(str::trim) {
+                        if width.is_empty() {
+                            continue;
+                        }
+                        let (title, width) = width
+                            .rsplit_once(':')
+                            .ok_or_else(|| invalid(name, "expected `<title>:<width>`"))?;
+                        config
+                            .column_widths
+                            .insert(title.trim().to_string(), parse_number(name, width)?);
+                    }
+                }
+                "max_rows" => {
+                    config.max_rows = Some(parse_number(name, value.unwrap_or_default())?)
+                }
+                "head" => config.head = Some(parse_number(name, value.unwrap_or_default())?),
+                "tail" => config.tail = Some(parse_number(name, value.unwrap_or_default())?),
+                "placeholder" => config.serialization_error_placeholder = value.map(String::from),
+                "timezone" => {
+                    config.display_timezone = value
+                        .unwrap_or_default()
+                        .parse()
+                        .map_err(|err: String| invalid(name, &err))?
+                }
+                "ellipsis" => {
+                    config.ellipsis = Some(
+                        value
+                            .unwrap_or_default()
+                            .parse()
+                            .map_err(|err: String| invalid(name, &err))?,
+                    )
+                }
+                "wide" => config.wide = parse_flag(name, value)?,
+                "verbosity" => config.verbosity = parse_number(name, value.unwrap_or_default())?,
+                "pretty" => config.pretty = parse_flag(name, value)?,
+                "hide_empty_columns" => config.hide_empty_columns = parse_flag(name, value)?,
+                "strict" => config.strict = parse_flag(name, value)?,
+                "raw" => config.raw_values = parse_flag(name, value)?,
+                "hyperlinks" => config.hyperlinks = parse_flag(name, value)?,
+                _ => return Err(invalid(name, "unknown option")),
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn invalid(option: &str, message: &str) -> StructTableError {
+    StructTableError::InvalidConfig {
+        option: option.to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn parse_number<T: FromStr>(option: &str, value: &str) -> Result<T, StructTableError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| invalid(option, "expected non-negative number"))
+}
+
+fn parse_flag(option: &str, value: Option<&str>) -> Result<bool, StructTableError> {
+    match value {
+        None => Ok(true),
+        Some(val) => val
+            .parse()
+            .map_err(|_| invalid(option, "expected `true` or `false`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_fields() {
+        assert_eq!(
+            OutputConfig::parse_fields(" id, Name,,"),
+            BTreeSet::from(["id".to_string(), "Name".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let config: OutputConfig =
+            "fields=id,name,@network;extra_fields=extra;wide; pretty=false;widths=name:10;max_rows=5;strict;ellipsis=start;timezone=utc;raw;verbosity=2;hyperlinks"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            config.fields,
+            BTreeSet::from(["id".to_string(), "name".to_string()])
+        );
+        assert_eq!(config.extra_fields, BTreeSet::from(["extra".to_string()]));
+        assert_eq!(config.groups, BTreeSet::from(["network".to_string()]));
+        assert!(config.wide);
+        assert!(!config.pretty);
+        assert!(config.strict);
+        assert!(config.raw_values);
+        assert_eq!(config.verbosity, 2);
+        assert!(config.hyperlinks);
+        assert_eq!(config.ellipsis, Some(crate::EllipsisPosition::Start));
+        assert_eq!(config.display_timezone, crate::DisplayTimezone::Utc);
+        assert_eq!(config.column_widths, HashMap::from([("name".into(), 10)]));
+        assert_eq!(config.max_rows, Some(5));
+
+        let config: OutputConfig = "".parse().unwrap();
+        assert!(config.fields.is_empty() && !config.wide);
+
+        assert_eq!(
+            "foo".parse::<OutputConfig>().unwrap_err(),
+            StructTableError::InvalidConfig {
+                option: "foo".into(),
+                message: "unknown option".into()
+            }
+        );
+        assert!("wide=yes".parse::<OutputConfig>().is_err());
+        assert!("head=-1".parse::<OutputConfig>().is_err());
+        assert!("widths=name".parse::<OutputConfig>().is_err());
+    }
+}
@@ -0,0 +1,204 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Formatting of the timestamp fields
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Timezone the timestamps are converted to before formatting
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DisplayTimezone {
+    /// Keep the timezone of the value
+    #[default]
+    Original,
+    /// Coordinated Universal Time
+    Utc,
+    /// Local timezone of the system (falls back to the original one when it can not be
+    /// determined)
+    Local,
+    /// Fixed offset from the UTC in seconds
+    Offset(i32),
+}
+
+/// Parse the timezone from `original`, `utc`, `local` or the `+HH:MM` / `-HH:MM` offset
+impl FromStr for DisplayTimezone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "original" => Ok(Self::Original),
+            "utc" | "UTC" => Ok(Self::Utc),
+            "local" => Ok(Self::Local),
+            other => {
+                let invalid = || format!("invalid timezone `{other}`");
+                let (sign, offset) = match (other.strip_prefix('+'), other.strip_prefix('-')) {
+                    (Some(offset), _) => (1, offset),
+                    (_, Some(offset)) => (-1, offset),
+                    _ => return Err(invalid()),
+                };
+                let (hours, minutes) = offset.split_once(':').ok_or_else(invalid)?;
+                let hours: i32 = hours.parse().map_err(|_| invalid())?;
+                let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+                if !(0..24).contains(&hours) || !(0..60).contains(&minutes) {
+                    return Err(invalid());
+                }
+                Ok(Self::Offset(sign * (hours * 3600 + minutes * 60)))
+            }
+        }
+    }
+}
+
+/// Timestamp rendered with the `datetime_format` field attribute
+///
+/// Implemented for the `chrono` (with the `chrono` feature) and `time` (with the `time` feature)
+/// timestamps. The format uses the syntax of the respective crate (i.e. `%Y-%m-%d %H:%M` for the
+/// `chrono` and `[year]-[month]-[day] [hour]:[minute]` for the `time`). Invalid formats result in
+/// the RFC 3339 representation.
+pub trait FormatDateTime {
+    /// Render the value converted to the `timezone` with the `format`
+    fn format_datetime(&self, format: &str, timezone: DisplayTimezone) -> String;
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impl {
+    use std::fmt::{Display, Write};
+
+    use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, TimeZone, Utc};
+
+    use super::{DisplayTimezone, FormatDateTime};
+
+    /// Format the timestamp falling back to RFC 3339 on the invalid format
+    fn format<Tz: TimeZone>(value: &DateTime<Tz>, format: &str) -> String
+    where
+        Tz::Offset: Display,
+    {
+        let mut res = String::new();
+        match write!(res, "{}", value.format(format)) {
+            Ok(()) => res,
+            Err(_) => value.to_rfc3339(),
+        }
+    }
+
+    impl<Tz: TimeZone> FormatDateTime for DateTime<Tz>
+    where
+        Tz::Offset: Display,
+    {
+        fn format_datetime(&self, fmt: &str, timezone: DisplayTimezone) -> String {
+            match timezone {
+                DisplayTimezone::Original => format(self, fmt),
+                DisplayTimezone::Utc => format(&self.with_timezone(&Utc), fmt),
+                DisplayTimezone::Local => format(&self.with_timezone(&Local), fmt),
+                DisplayTimezone::Offset(seconds) => match FixedOffset::east_opt(seconds) {
+                    Some(offset) => format(&self.with_timezone(&offset), fmt),
+                    None => format(self, fmt),
+                },
+            }
+        }
+    }
+
+    /// Naive timestamps are formatted without the timezone conversion
+    impl FormatDateTime for NaiveDateTime {
+        fn format_datetime(&self, fmt: &str, _timezone: DisplayTimezone) -> String {
+            let mut res = String::new();
+            match write!(res, "{}", self.format(fmt)) {
+                Ok(()) => res,
+                Err(_) => self.to_string(),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impl {
+    use time::format_description::well_known::Rfc3339;
+    use time::{OffsetDateTime, UtcOffset};
+
+    use super::{DisplayTimezone, FormatDateTime};
+
+    impl FormatDateTime for OffsetDateTime {
+        fn format_datetime(&self, fmt: &str, timezone: DisplayTimezone) -> String {
+            let value = match timezone {
+                DisplayTimezone::Original => *self,
+                DisplayTimezone::Utc => self.to_offset(UtcOffset::UTC),
+                DisplayTimezone::Local => UtcOffset::current_local_offset()
+                    .map(|offset| self.to_offset(offset))
+                    .unwrap_or(*self),
+                DisplayTimezone::Offset(seconds) => UtcOffset::from_whole_seconds(seconds)
+                    .map(|offset| self.to_offset(offset))
+                    .unwrap_or(*self),
+            };
+            time::format_description::parse_borrowed::<2>(fmt)
+                .ok()
+                .and_then(|description| value.format(&description).ok())
+                .or_else(|| value.format(&Rfc3339).ok())
+                .unwrap_or_else(|| value.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!("utc".parse(), Ok(DisplayTimezone::Utc));
+        assert_eq!("+02:30".parse(), Ok(DisplayTimezone::Offset(9000)));
+        assert_eq!("-01:00".parse(), Ok(DisplayTimezone::Offset(-3600)));
+        assert!("01:00".parse::<DisplayTimezone>().is_err());
+        assert!("+25:00".parse::<DisplayTimezone>().is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono() {
+        let value =
+            chrono::DateTime::parse_from_rfc3339("2024-01-02T03:04:05.123456789+02:00").unwrap();
+        assert_eq!(
+            value.format_datetime("%Y-%m-%d %H:%M", DisplayTimezone::Original),
+            "2024-01-02 03:04"
+        );
+        assert_eq!(
+            value.format_datetime("%Y-%m-%d %H:%M %:z", DisplayTimezone::Utc),
+            "2024-01-02 01:04 +00:00"
+        );
+        assert_eq!(
+            value.format_datetime("%H:%M", DisplayTimezone::Offset(-3600)),
+            "00:04"
+        );
+        assert_eq!(
+            value.format_datetime("%Q", DisplayTimezone::Original),
+            "2024-01-02T03:04:05.123456789+02:00"
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time() {
+        let value = time::OffsetDateTime::from_unix_timestamp(1_704_157_445)
+            .unwrap()
+            .to_offset(time::UtcOffset::from_hms(2, 0, 0).unwrap());
+        assert_eq!(
+            value.format_datetime("[year]-[month]-[day] [hour]:[minute]", DisplayTimezone::Utc),
+            "2024-01-02 01:04"
+        );
+        assert_eq!(
+            value.format_datetime("[invalid", DisplayTimezone::Original),
+            "2024-01-02T03:04:05+02:00"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tables of the dynamic maps with the json values
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+use serde_json::Value;
+
+use crate::{extract_field_data, StructTable, StructTableOptions};
+
+/// Render the json value of the dynamic map entry
+///
+/// Strings are returned without quotes, other scalars as is. Nested objects and arrays are
+/// serialized compact or pretty depending on the `pretty_mode`. `null` is treated as a missing
+/// value.
+fn render_value<O: StructTableOptions>(options: &O, field: &str, value: &Value) -> Option<String> {
+    match extract_field_data(options, field, value.clone()) {
+        Value::Null => None,
+        Value::String(val) => Some(val),
+        val @ (Value::Bool(_) | Value::Number(_)) => Some(val.to_string()),
+        val => match options.pretty_mode() {
+            true => serde_json::to_string_pretty(&val),
+            false => serde_json::to_string(&val),
+        }
+        .map_or_else(
+            |err| Some(options.serialization_error_placeholder(field, &err)),
+            Some,
+        ),
+    }
+}
+
+/// Status of the dynamic map (string value of the `status` key)
+fn map_status<'a, I: Iterator<Item = (&'a String, &'a Value)>>(entries: I) -> Option<String> {
+    entries
+        .filter(|(key, _)| key.to_lowercase() == "status")
+        .find_map(|(_, value)| match value {
+            Value::Null => None,
+            Value::String(val) => Some(val.clone()),
+            val => Some(val.to_string()),
+        })
+}
+
+impl StructTable for BTreeMap<String, Value> {
+    fn instance_headers<O: StructTableOptions>(&self, options: &O) -> Option<Vec<String>> {
+        Some(
+            self.keys()
+                .filter(|key| options.should_return_field(key, false))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    fn data<O: StructTableOptions>(&self, options: &O) -> Vec<Option<String>> {
+        self.iter()
+            .filter(|(key, _)| options.should_return_field(key, false))
+            .map(|(key, value)| render_value(options, key, value))
+            .collect()
+    }
+
+    fn status(&self) -> Option<String> {
+        map_status(self.iter())
+    }
+}
+
+/// Entries of the `HashMap` are sorted by the key to produce stable output
+impl<S: BuildHasher> StructTable for HashMap<String, Value, S> {
+    fn instance_headers<O: StructTableOptions>(&self, options: &O) -> Option<Vec<String>> {
+        let mut keys: Vec<&String> = self
+            .keys()
+            .filter(|key| options.should_return_field(key, false))
+            .collect();
+        keys.sort();
+        Some(keys.into_iter().cloned().collect())
+    }
+
+    fn data<O: StructTableOptions>(&self, options: &O) -> Vec<Option<String>> {
+        let mut entries: Vec<(&String, &Value)> = self
+            .iter()
+            .filter(|(key, _)| options.should_return_field(key, false))
+            .collect();
+        entries.sort_by_key(|(key, _)| *key);
+        entries
+            .into_iter()
+            .map(|(key, value)| render_value(options, key, value))
+            .collect()
+    }
+
+    fn status(&self) -> Option<String> {
+        let mut entries: Vec<(&String, &Value)> = self.iter().collect();
+        entries.sort_by_key(|(key, _)| *key);
+        map_status(entries.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_list_table, build_table, OutputConfig};
+    use serde_json::json;
+
+    fn sample() -> HashMap<String, Value> {
+        serde_json::from_value(json!({
+            "name": "vm",
+            "size": 3,
+            "status": "ACTIVE",
+            "nested": {"a": 1},
+            "empty": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_hashmap() {
+        let data = sample();
+        assert_eq!(data.status(), Some("ACTIVE".into()));
+        assert_eq!(
+            build_table(&data, &OutputConfig::default()).1,
+            vec![
+                vec!["name".to_string(), "vm".to_string()],
+                vec!["nested".to_string(), "{\"a\":1}".to_string()],
+                vec!["size".to_string(), "3".to_string()],
+                vec!["status".to_string(), "ACTIVE".to_string()],
+            ]
+        );
+        let config = OutputConfig {
+            pretty: true,
+            fields: ["nested".to_string()].into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_table(&data, &config).1,
+            vec![vec!["nested".to_string(), "{\n  \"a\": 1\n}".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_btreemap_list() {
+        let first: BTreeMap<String, Value> =
+            serde_json::from_value(json!({"name": "a", "tags": ["x"]})).unwrap();
+        let second: BTreeMap<String, Value> =
+            serde_json::from_value(json!({"name": "b", "size": 1.5})).unwrap();
+        assert_eq!(
+            build_list_table([first, second].iter(), &OutputConfig::default()),
+            (
+                vec!["name".into(), "tags".into(), "size".into()],
+                vec![
+                    vec!["a".into(), "[\"x\"]".into(), " ".into()],
+                    vec!["b".into(), " ".into(), "1.5".into()],
+                ]
+            )
+        );
+    }
+}
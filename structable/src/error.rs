@@ -0,0 +1,165 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Errors of the table building
+
+use std::fmt;
+
+/// Table building error
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StructTableError {
+    /// Field data can not be serialized
+    Serialization {
+        /// Field title
+        field: String,
+        /// Serialization error message
+        message: String,
+    },
+    /// Json pointer (or other query) does not resolve to the field data
+    DataNotFound {
+        /// Field title
+        field: String,
+        /// The query that failed to resolve
+        query: String,
+    },
+    /// Output configuration can not be parsed
+    InvalidConfig {
+        /// Configuration option
+        option: String,
+        /// Error message
+        message: String,
+    },
+}
+
+impl fmt::Display for StructTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Serialization { field, message } => {
+                write!(
+                    f,
+                    "error serializing data of the `{field}` field: {message}"
+                )
+            }
+            Self::DataNotFound { field, query } => {
+                write!(f, "`{query}` does not resolve data of the `{field}` field")
+            }
+            Self::InvalidConfig { option, message } => {
+                write!(f, "invalid `{option}` output option: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StructTableError {}
+
+/// Non-fatal issue encountered while building the table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Warning {
+    /// Requested field does not match any column
+    UnknownField {
+        /// Requested field title
+        field: String,
+    },
+    /// Field data error replaced with the placeholder
+    Data(StructTableError),
+    /// Cells of the column were truncated to the maximal column width
+    Truncated {
+        /// Column title
+        field: String,
+        /// Number of truncated cells
+        cells: usize,
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownField { field } => write!(f, "unknown field `{field}`"),
+            Self::Data(err) => err.fmt(f),
+            Self::Truncated { field, cells } => {
+                write!(f, "{cells} value(s) of the `{field}` field truncated")
+            }
+        }
+    }
+}
+
+/// Violated invariant of the built table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TableIssue {
+    /// Number of the row cells differs from the number of headers
+    RowLength {
+        /// Row index
+        row: usize,
+        /// Number of the row cells
+        cells: usize,
+        /// Number of the headers
+        headers: usize,
+    },
+    /// Header title is present multiple times
+    DuplicateHeader {
+        /// Column title
+        title: String,
+    },
+    /// Expected column is not present in the table
+    UnknownColumn {
+        /// Column title
+        title: String,
+    },
+    /// Values of the key columns are not unique
+    DuplicateKey {
+        /// Key values joined with `/`
+        key: String,
+        /// Indexes of the rows sharing the key
+        rows: Vec<usize>,
+    },
+}
+
+impl fmt::Display for TableIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RowLength {
+                row,
+                cells,
+                headers,
+            } => write!(f, "row {row} has {cells} cell(s) for {headers} header(s)"),
+            Self::DuplicateHeader { title } => write!(f, "duplicate column `{title}`"),
+            Self::UnknownColumn { title } => write!(f, "unknown column `{title}`"),
+            Self::DuplicateKey { key, rows } => {
+                write!(f, "key `{key}` is not unique (rows {rows:?})")
+            }
+        }
+    }
+}
+
+/// Report of the table validation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Found issues
+    pub issues: Vec<TableIssue>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid table: ")?;
+        for (idx, issue) in self.issues.iter().enumerate() {
+            if idx > 0 {
+                write!(f, "; ")?;
+            }
+            issue.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
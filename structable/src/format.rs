@@ -0,0 +1,578 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Plain text formatters of the built tables
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::GroupedTable;
+
+/// Wrap the `text` into the OSC 8 terminal hyperlink pointing to the `url`
+///
+/// Terminals without the support show the plain text.
+pub fn hyperlink(url: &str, text: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+/// Representation of the missing (`None`) values depending on the output format
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct NullPolicy {
+    /// Placeholder in the text tables
+    #[serde(default = "NullPolicy::default_text")]
+    pub text: String,
+    /// Placeholder in the CSV (TSV) output
+    #[serde(default)]
+    pub csv: String,
+}
+
+impl NullPolicy {
+    fn default_text() -> String {
+        String::from(" ")
+    }
+}
+
+impl Default for NullPolicy {
+    fn default() -> Self {
+        Self {
+            text: Self::default_text(),
+            csv: String::new(),
+        }
+    }
+}
+
+/// Format every row of the list table as a single line of `key=value` pairs
+///
+/// Values that are empty or contain whitespace, quotes or `=` are double quoted with quotes and
+/// backslashes escaped, newlines are replaced with `\n`.
+pub fn format_key_value_rows(headers: &[String], rows: &[Vec<String>]) -> Vec<String> {
+    rows.iter()
+        .map(|row| {
+            headers
+                .iter()
+                .zip(row.iter())
+                .map(|(key, value)| format!("{}={}", key, quote_value(value.trim())))
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+/// Quoting style of the CSV fields
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CsvQuoteStyle {
+    /// Quote every field
+    Always,
+    /// Quote only fields containing delimiter, quotes or line breaks
+    #[default]
+    Necessary,
+    /// Never quote fields
+    Never,
+}
+
+/// CSV (TSV) output options
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// Field delimiter
+    pub delimiter: char,
+    /// Quoting style
+    pub quote_style: CsvQuoteStyle,
+    /// Record terminator
+    pub terminator: String,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            quote_style: CsvQuoteStyle::default(),
+            terminator: String::from("\n"),
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Options for the tab separated values
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: '\t',
+            ..Default::default()
+        }
+    }
+}
+
+/// Format the table as CSV with the header record followed by the rows
+///
+/// Quoted fields are escaped according to the RFC 4180 (embedded quotes are doubled).
+pub fn format_csv(headers: &[String], rows: &[Vec<String>], options: &CsvOptions) -> String {
+    let mut res = String::new();
+    for record in std::iter::once(headers).chain(rows.iter().map(Vec::as_slice)) {
+        for (idx, field) in record.iter().enumerate() {
+            if idx > 0 {
+                res.push(options.delimiter);
+            }
+            res.push_str(&csv_field(field, options));
+        }
+        res.push_str(&options.terminator);
+    }
+    res
+}
+
+/// Format the grouped table as CSV with the group column in front
+///
+/// CSV has no notion of sections, therefore the group label is repeated in every record.
+pub fn format_grouped_csv(table: &GroupedTable, options: &CsvOptions) -> String {
+    let (headers, rows) = table.flatten();
+    format_csv(&headers, &rows, options)
+}
+
+/// Format the table as the GitHub flavored Markdown table
+///
+/// Cell values are escaped so that they do not break the table: pipes are escaped, line breaks
+/// are converted to `<br>` and leading hyphens are escaped to not be taken as the list items.
+pub fn format_markdown(headers: &[String], rows: &[Vec<String>]) -> String {
+    let mut res = markdown_line(headers.iter().map(|x| markdown_cell(x)));
+    res.push_str(&markdown_line(headers.iter().map(|_| String::from("---"))));
+    for row in rows {
+        res.push_str(&markdown_line(row.iter().map(|x| markdown_cell(x))));
+    }
+    res
+}
+
+/// Format the grouped table as the Markdown section (`### <group>: <label>`) per group
+///
+/// Every section contains its own table with the same columns.
+pub fn format_grouped_markdown(table: &GroupedTable) -> String {
+    let mut res = String::new();
+    for (idx, (label, rows)) in table.groups.iter().enumerate() {
+        if idx > 0 {
+            res.push('\n');
+        }
+        res.push_str(&format!(
+            "### {}\n\n",
+            markdown_cell(&format!("{}: {}", table.group_by, label))
+        ));
+        res.push_str(&format_markdown(&table.headers, rows));
+    }
+    res
+}
+
+/// Join cells into the Markdown table line
+fn markdown_line<I: Iterator<Item = String>>(cells: I) -> String {
+    let mut line = String::from("|");
+    for cell in cells {
+        line.push(' ');
+        line.push_str(&cell);
+        line.push_str(" |");
+    }
+    line.push('\n');
+    line
+}
+
+/// HTML output options
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HtmlOptions {
+    /// `id` of the table element
+    pub id: Option<String>,
+    /// CSS class of the table element
+    pub class: Option<String>,
+    /// CSS classes of the header and data cells by the column title
+    pub column_classes: HashMap<String, String>,
+    /// Title of the column which value is exposed as the `data-status` attribute of the row
+    pub status_column: Option<String>,
+}
+
+/// Format the table as the HTML table element
+///
+/// All titles, values and attributes are HTML escaped.
+pub fn format_html(headers: &[String], rows: &[Vec<String>], options: &HtmlOptions) -> String {
+    let classes: Vec<Option<&String>> = headers
+        .iter()
+        .map(|hdr| options.column_classes.get(hdr))
+        .collect();
+    let status_idx = options
+        .status_column
+        .as_ref()
+        .and_then(|status| headers.iter().position(|hdr| hdr == status));
+    let mut res = html_table_head(headers, &classes, options);
+    res.push_str("<tbody>\n");
+    for row in rows {
+        res.push_str(&html_row(row, &classes, status_idx));
+    }
+    res.push_str("</tbody>\n</table>\n");
+    res
+}
+
+/// Format the grouped table as the HTML table element with the `<tbody>` per group
+///
+/// Every group body starts with the row of the `group` class containing the group label.
+pub fn format_grouped_html(table: &GroupedTable, options: &HtmlOptions) -> String {
+    let headers = &table.headers;
+    let classes: Vec<Option<&String>> = headers
+        .iter()
+        .map(|hdr| options.column_classes.get(hdr))
+        .collect();
+    let status_idx = options
+        .status_column
+        .as_ref()
+        .and_then(|status| headers.iter().position(|hdr| hdr == status));
+    let mut res = html_table_head(headers, &classes, options);
+    for (label, rows) in table.groups.iter() {
+        res.push_str(&format!(
+            "<tbody>\n<tr class=\"group\"><th colspan=\"{}\">{}: {}</th></tr>\n",
+            headers.len().max(1),
+            html_escape(&table.group_by),
+            html_escape(label)
+        ));
+        for row in rows {
+            res.push_str(&html_row(row, &classes, status_idx));
+        }
+        res.push_str("</tbody>\n");
+    }
+    res.push_str("</table>\n");
+    res
+}
+
+/// Opening of the HTML table element with the header row
+fn html_table_head(
+    headers: &[String],
+    classes: &[Option<&String>],
+    options: &HtmlOptions,
+) -> String {
+    let mut res = String::from("<table");
+    if let Some(id) = &options.id {
+        res.push_str(&format!(" id=\"{}\"", html_escape(id)));
+    }
+    if let Some(class) = &options.class {
+        res.push_str(&format!(" class=\"{}\"", html_escape(class)));
+    }
+    res.push_str(">\n<thead>\n<tr>");
+    for (hdr, class) in headers.iter().zip(classes.iter()) {
+        res.push_str(&html_cell("th", hdr, *class));
+    }
+    res.push_str("</tr>\n</thead>\n");
+    res
+}
+
+/// HTML table row with the optional `data-status` attribute
+fn html_row(row: &[String], classes: &[Option<&String>], status_idx: Option<usize>) -> String {
+    let mut res = String::from("<tr");
+    if let Some(status) = status_idx.and_then(|idx| row.get(idx)) {
+        let status = status.trim();
+        if !status.is_empty() {
+            res.push_str(&format!(
+                " data-status=\"{}\"",
+                html_escape(&status.to_lowercase())
+            ));
+        }
+    }
+    res.push('>');
+    for (cell, class) in row.iter().zip(classes.iter()) {
+        res.push_str(&html_cell("td", cell, *class));
+    }
+    res.push_str("</tr>\n");
+    res
+}
+
+/// HTML table cell with the optional CSS class
+fn html_cell(tag: &str, value: &str, class: Option<&String>) -> String {
+    match class {
+        Some(class) => format!(
+            "<{tag} class=\"{}\">{}</{tag}>",
+            html_escape(class),
+            html_escape(value)
+        ),
+        None => format!("<{tag}>{}</{tag}>", html_escape(value)),
+    }
+}
+
+/// Escape the HTML special characters
+fn html_escape(value: &str) -> String {
+    let mut res = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            '\'' => res.push_str("&#39;"),
+            _ => res.push(c),
+        }
+    }
+    res
+}
+
+/// Escape the Markdown table cell value
+fn markdown_cell(value: &str) -> String {
+    let value = value.trim();
+    let mut res = String::with_capacity(value.len());
+    if value.starts_with('-') {
+        res.push('\\');
+    }
+    let mut lines = value.lines().peekable();
+    while let Some(line) = lines.next() {
+        res.push_str(&line.replace('|', "\\|"));
+        if lines.peek().is_some() {
+            res.push_str("<br>");
+        }
+    }
+    res
+}
+
+/// Format every row of the list table as a linear sentence of `column: value` pairs
+///
+/// Intended for screen readers: no alignment whitespace or box drawing characters are emitted,
+/// multiline values are joined with spaces and empty values are omitted.
+pub fn format_linear(headers: &[String], rows: &[Vec<String>]) -> Vec<String> {
+    rows.iter()
+        .map(|row| {
+            let mut line = headers
+                .iter()
+                .zip(row.iter())
+                .filter_map(|(key, value)| {
+                    let value = value.split_whitespace().collect::<Vec<&str>>().join(" ");
+                    (!value.is_empty()).then(|| format!("{key}: {value}"))
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            line.push('.');
+            line
+        })
+        .collect()
+}
+
+/// Quote and escape the CSV field according to the options
+fn csv_field(value: &str, options: &CsvOptions) -> String {
+    let quote = match options.quote_style {
+        CsvQuoteStyle::Always => true,
+        CsvQuoteStyle::Never => false,
+        CsvQuoteStyle::Necessary => value
+            .chars()
+            .any(|c| c == options.delimiter || c == '"' || c == '\n' || c == '\r'),
+    };
+    if quote {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Quote the value when necessary
+fn quote_value(value: &str) -> String {
+    if !value.is_empty()
+        && !value
+            .chars()
+            .any(|c| c.is_whitespace() || c == '"' || c == '=' || c == '\\')
+    {
+        return value.to_string();
+    }
+    let mut res = String::with_capacity(value.len() + 2);
+    res.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => {}
+            _ => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv() {
+        let headers = vec!["ID".to_string(), "Name".to_string()];
+        let rows = vec![
+            vec!["1".to_string(), "Doo, Scooby".to_string()],
+            vec!["2".to_string(), "say \"hi\"\nagain".to_string()],
+        ];
+        assert_eq!(
+            format_csv(&headers, &rows, &CsvOptions::default()),
+            "ID,Name\n1,\"Doo, Scooby\"\n2,\"say \"\"hi\"\"\nagain\"\n"
+        );
+        assert_eq!(
+            format_csv(
+                &headers,
+                &rows[..1],
+                &CsvOptions {
+                    quote_style: CsvQuoteStyle::Always,
+                    terminator: "\r\n".into(),
+                    ..Default::default()
+                }
+            ),
+            "\"ID\",\"Name\"\r\n\"1\",\"Doo, Scooby\"\r\n"
+        );
+        assert_eq!(
+            format_csv(&headers, &rows[..1], &CsvOptions::tsv()),
+            "ID\tName\n1\tDoo, Scooby\n"
+        );
+        assert_eq!(
+            format_csv(
+                &headers,
+                &rows[..1],
+                &CsvOptions {
+                    quote_style: CsvQuoteStyle::Never,
+                    ..Default::default()
+                }
+            ),
+            "ID,Name\n1,Doo, Scooby\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        let headers = vec!["ID".to_string(), "Name|Alias".to_string()];
+        let rows = vec![
+            vec!["1".to_string(), "a | b".to_string()],
+            vec!["-2".to_string(), "line1\nline2".to_string()],
+            vec![" ".to_string(), "x".to_string()],
+        ];
+        assert_eq!(
+            format_markdown(&headers, &rows),
+            "| ID | Name\\|Alias |\n\
+             | --- | --- |\n\
+             | 1 | a \\| b |\n\
+             | \\-2 | line1<br>line2 |\n\
+             |  | x |\n"
+        );
+    }
+
+    #[test]
+    fn test_html() {
+        let headers = vec!["Name".to_string(), "Status".to_string()];
+        let rows = vec![
+            vec!["<b>".to_string(), "ACTIVE".to_string()],
+            vec!["x".to_string(), " ".to_string()],
+        ];
+        let options = HtmlOptions {
+            id: Some("servers".into()),
+            class: Some("table dark".into()),
+            column_classes: HashMap::from([("Status".into(), "status".into())]),
+            status_column: Some("Status".into()),
+        };
+        assert_eq!(
+            format_html(&headers, &rows, &options),
+            "<table id=\"servers\" class=\"table dark\">\n\
+             <thead>\n<tr><th>Name</th><th class=\"status\">Status</th></tr>\n</thead>\n\
+             <tbody>\n\
+             <tr data-status=\"active\"><td>&lt;b&gt;</td><td class=\"status\">ACTIVE</td></tr>\n\
+             <tr><td>x</td><td class=\"status\"> </td></tr>\n\
+             </tbody>\n</table>\n"
+        );
+    }
+
+    #[test]
+    fn test_grouped() {
+        let table = GroupedTable {
+            group_by: "Zone".into(),
+            headers: vec!["Name".into(), "Status".into()],
+            groups: vec![
+                (
+                    "z1".into(),
+                    vec![
+                        vec!["a".into(), "ACTIVE".into()],
+                        vec!["c".into(), "ERROR".into()],
+                    ],
+                ),
+                ("<z2>".into(), vec![vec!["b | x".into(), " ".into()]]),
+            ],
+            omitted_rows: 0,
+        };
+        assert_eq!(
+            format_grouped_markdown(&table),
+            "### Zone: z1\n\n\
+             | Name | Status |\n\
+             | --- | --- |\n\
+             | a | ACTIVE |\n\
+             | c | ERROR |\n\
+             \n\
+             ### Zone: <z2>\n\n\
+             | Name | Status |\n\
+             | --- | --- |\n\
+             | b \\| x |  |\n"
+        );
+        assert_eq!(
+            format_grouped_html(
+                &table,
+                &HtmlOptions {
+                    status_column: Some("Status".into()),
+                    ..Default::default()
+                }
+            ),
+            "<table>\n\
+             <thead>\n<tr><th>Name</th><th>Status</th></tr>\n</thead>\n\
+             <tbody>\n\
+             <tr class=\"group\"><th colspan=\"2\">Zone: z1</th></tr>\n\
+             <tr data-status=\"active\"><td>a</td><td>ACTIVE</td></tr>\n\
+             <tr data-status=\"error\"><td>c</td><td>ERROR</td></tr>\n\
+             </tbody>\n\
+             <tbody>\n\
+             <tr class=\"group\"><th colspan=\"2\">Zone: &lt;z2&gt;</th></tr>\n\
+             <tr><td>b | x</td><td> </td></tr>\n\
+             </tbody>\n\
+             </table>\n"
+        );
+        assert_eq!(
+            format_grouped_csv(&table, &CsvOptions::default()),
+            "Zone,Name,Status\nz1,a,ACTIVE\nz1,c,ERROR\n<z2>,b | x, \n"
+        );
+    }
+
+    #[test]
+    fn test_linear() {
+        let headers = vec!["ID".to_string(), "Name".to_string(), "Data".to_string()];
+        let rows = vec![
+            vec!["1".to_string(), "Scooby  Doo".to_string(), " ".to_string()],
+            vec![
+                "2".to_string(),
+                "John".to_string(),
+                "{\n  \"a\": 1\n}".to_string(),
+            ],
+        ];
+        assert_eq!(
+            format_linear(&headers, &rows),
+            vec![
+                "ID: 1, Name: Scooby Doo.".to_string(),
+                "ID: 2, Name: John, Data: { \"a\": 1 }.".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_key_value_rows() {
+        let headers = vec!["ID".to_string(), "Name".to_string(), "Data".to_string()];
+        let rows = vec![
+            vec!["1".to_string(), "Scooby Doo".to_string(), " ".to_string()],
+            vec![
+                "2".to_string(),
+                "John".to_string(),
+                "{\n  \"a\": \"b=c\"\n}".to_string(),
+            ],
+        ];
+        assert_eq!(
+            format_key_value_rows(&headers, &rows),
+            vec![
+                "ID=1 Name=\"Scooby Doo\" Data=\"\"".to_string(),
+                "ID=2 Name=John Data=\"{\\n  \\\"a\\\": \\\"b=c\\\"\\n}\"".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,213 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! List tables grouped into the labeled sections
+
+use crate::{build_list_table_with_null, titles_match, StructTable, StructTableOptions};
+
+/// List table split into sections by the value of the group column
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GroupedTable {
+    /// Title of the column the rows are grouped by
+    pub group_by: String,
+    /// Column headers (without the group column)
+    pub headers: Vec<String>,
+    /// Section labels with their rows in the order of the first appearance
+    pub groups: Vec<(String, Vec<Vec<String>>)>,
+    /// Number of rows omitted due to the [`StructTableOptions::max_rows`]
+    pub omitted_rows: usize,
+}
+
+impl GroupedTable {
+    /// Flatten the sections back into a single table with the group column in front
+    ///
+    /// Useful for the output formats without the notion of sections (i.e. CSV). Markdown and HTML
+    /// keep the sections with [`format_grouped_markdown`](crate::format_grouped_markdown) and
+    /// [`format_grouped_html`](crate::format_grouped_html).
+    pub fn flatten(&self) -> (Vec<String>, Vec<Vec<String>>) {
+        let headers = std::iter::once(self.group_by.clone())
+            .chain(self.headers.iter().cloned())
+            .collect();
+        let rows = self
+            .groups
+            .iter()
+            .flat_map(|(label, rows)| {
+                rows.iter().map(move |row| {
+                    std::iter::once(label.clone())
+                        .chain(row.iter().cloned())
+                        .collect()
+                })
+            })
+            .collect();
+        (headers, rows)
+    }
+}
+
+/// Build a list table grouped by the value of the `group_by` column
+///
+/// Rows are built the same way as in [`build_list_table`](crate::build_list_table). The group
+/// column is removed from the rows and its value is used as the section label. When the column is
+/// not returned all rows are placed into the single section with the empty label.
+///
+/// Instead of the trailer row only the first [`StructTableOptions::max_rows`] rows (in the order
+/// of the sections) are kept and the number of the omitted ones is set in the `omitted_rows`.
+pub fn build_grouped_table<I, T, O>(data: I, options: &O, group_by: &str) -> GroupedTable
+where
+    I: Iterator<Item = T>,
+    T: StructTable,
+    O: StructTableOptions,
+{
+    let (mut headers, rows) = build_list_table_with_null(
+        data,
+        options,
+        &options.null_policy().text,
+        None,
+        None,
+        |_: &T, row| row,
+    );
+    let mut groups: Vec<(String, Vec<Vec<String>>)> = Vec::new();
+    let renamed = options.column_title(group_by);
+    match headers
+        .iter()
+        .position(|hdr| titles_match(hdr, group_by) || renamed.as_ref() == Some(hdr))
+    {
+        Some(idx) => {
+            let group_by = headers.remove(idx);
+            for mut row in rows {
+                let label = match idx < row.len() {
+                    true => row.remove(idx),
+                    false => String::new(),
+                };
+                match groups.iter_mut().find(|(name, _)| *name == label) {
+                    Some((_, group)) => group.push(row),
+                    None => groups.push((label, vec![row])),
+                }
+            }
+            limit_rows(
+                GroupedTable {
+                    group_by,
+                    headers,
+                    groups,
+                    omitted_rows: 0,
+                },
+                options.max_rows(),
+            )
+        }
+        None => {
+            if !rows.is_empty() {
+                groups.push((String::new(), rows));
+            }
+            limit_rows(
+                GroupedTable {
+                    group_by: group_by.to_string(),
+                    headers,
+                    groups,
+                    omitted_rows: 0,
+                },
+                options.max_rows(),
+            )
+        }
+    }
+}
+
+/// Keep only the first `max_rows` rows dropping the sections left empty
+fn limit_rows(mut table: GroupedTable, max_rows: Option<usize>) -> GroupedTable {
+    let mut remaining = max_rows.unwrap_or(usize::MAX);
+    for (_, rows) in table.groups.iter_mut() {
+        let keep = rows.len().min(remaining);
+        table.omitted_rows += rows.len() - keep;
+        rows.truncate(keep);
+        remaining -= keep;
+    }
+    table.groups.retain(|(_, rows)| !rows.is_empty());
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OutputConfig;
+
+    #[derive(StructTable)]
+    struct Server {
+        name: &'static str,
+        #[structable(title = "Zone")]
+        zone: &'static str,
+    }
+
+    #[test]
+    fn test_grouped() {
+        let data = [
+            Server {
+                name: "a",
+                zone: "z1",
+            },
+            Server {
+                name: "b",
+                zone: "z2",
+            },
+            Server {
+                name: "c",
+                zone: "z1",
+            },
+        ];
+        let table = build_grouped_table(data.iter(), &OutputConfig::default(), "zone");
+        assert_eq!(
+            table,
+            GroupedTable {
+                group_by: "Zone".into(),
+                headers: vec!["name".into()],
+                groups: vec![
+                    ("z1".into(), vec![vec!["a".into()], vec!["c".into()]]),
+                    ("z2".into(), vec![vec!["b".into()]]),
+                ],
+                omitted_rows: 0,
+            }
+        );
+        assert_eq!(
+            table.flatten(),
+            (
+                vec!["Zone".into(), "name".into()],
+                vec![
+                    vec!["z1".into(), "a".into()],
+                    vec!["z1".into(), "c".into()],
+                    vec!["z2".into(), "b".into()],
+                ]
+            )
+        );
+
+        let table = build_grouped_table(data.iter(), &OutputConfig::default(), "missing");
+        assert_eq!(table.headers, vec!["name".to_string(), "Zone".to_string()]);
+        assert_eq!(table.groups.len(), 1);
+        assert_eq!(table.groups[0].1.len(), 3);
+
+        // No trailer row, the omitted rows are counted instead
+        let config = OutputConfig {
+            max_rows: Some(1),
+            ..Default::default()
+        };
+        let table = build_grouped_table(data.iter(), &config, "zone");
+        assert_eq!(
+            table.groups,
+            vec![("z1".to_string(), vec![vec!["a".to_string()]])]
+        );
+        assert_eq!(table.omitted_rows, 2);
+        let table = build_grouped_table(data.iter(), &config, "name");
+        assert_eq!(
+            table.groups,
+            vec![("a".to_string(), vec![vec!["z1".to_string()]])]
+        );
+        assert_eq!(table.omitted_rows, 2);
+    }
+}
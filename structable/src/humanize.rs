@@ -0,0 +1,208 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Helpers making rendered values human friendly
+
+use std::time::Duration;
+
+/// Insert the separator between groups of 3 digits of the integer value
+///
+/// Values that are not integers are returned unchanged.
+pub fn group_digits(value: String, separator: char) -> String {
+    let (sign, digits) = match value.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", value.as_str()),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return value;
+    }
+    let mut res = String::from(sign);
+    for (idx, c) in digits.chars().enumerate() {
+        if idx > 0 && (digits.len() - idx) % 3 == 0 {
+            res.push(separator);
+        }
+        res.push(c);
+    }
+    res
+}
+
+/// Binary units of the byte sizes
+const BYTE_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+/// Render the integer byte count with the binary unit (i.e. `1.4 GiB`, `512 KiB`)
+///
+/// Values that are not non-negative integers are returned unchanged.
+pub fn humanize_bytes(value: String) -> String {
+    let Ok(bytes) = value.trim().parse::<u64>() else {
+        return value;
+    };
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < BYTE_UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    let rendered = match unit {
+        0 => bytes.to_string(),
+        _ => format!("{size:.1}"),
+    };
+    format!(
+        "{} {}",
+        rendered.strip_suffix(".0").unwrap_or(&rendered),
+        BYTE_UNITS[unit]
+    )
+}
+
+/// Value of the field marked with the `duration` attribute
+///
+/// Implemented for the `std::time::Duration` and integer number of seconds.
+pub trait DurationValue {
+    /// Value as the duration (`None` when it can not be represented, i.e. negative number)
+    fn as_duration(&self) -> Option<Duration>;
+
+    /// Representation of the value when it is not humanized
+    fn raw_value(&self) -> String;
+}
+
+impl DurationValue for Duration {
+    fn as_duration(&self) -> Option<Duration> {
+        Some(*self)
+    }
+
+    fn raw_value(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+macro_rules! impl_duration_value {
+    ($($ty:ty),*) => {
+        $(
+            impl DurationValue for $ty {
+                fn as_duration(&self) -> Option<Duration> {
+                    u64::try_from(*self).ok().map(Duration::from_secs)
+                }
+
+                fn raw_value(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_duration_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Render the duration with its two most significant units (i.e. `2h 15m`, `3d 4h`, `45s`)
+///
+/// Durations shorter than a second are rendered in milliseconds.
+pub fn humanize_duration(duration: Duration) -> String {
+    const UNITS: [(&str, u64); 4] = [("d", 86400), ("h", 3600), ("m", 60), ("s", 1)];
+    let mut secs = duration.as_secs();
+    if secs == 0 {
+        return format!("{}ms", duration.as_millis());
+    }
+    let mut parts: Vec<String> = Vec::new();
+    for (unit, size) in UNITS {
+        let count = secs / size;
+        secs %= size;
+        if count > 0 {
+            parts.push(format!("{count}{unit}"));
+        } else if !parts.is_empty() {
+            break;
+        }
+        if parts.len() == 2 {
+            break;
+        }
+    }
+    parts.join(" ")
+}
+
+/// Render the value of the field marked with the `duration` attribute
+#[doc(hidden)]
+pub fn format_duration<T: DurationValue + ?Sized>(value: &T, humanize: bool) -> String {
+    match (humanize, value.as_duration()) {
+        (true, Some(duration)) => humanize_duration(duration),
+        _ => value.raw_value(),
+    }
+}
+
+/// Capitalize the first letter of every word and lowercase the rest (`SHUTOFF` => `Shutoff`)
+///
+/// Words are separated by the whitespace, `_` and `-` (which are kept).
+pub fn titlecase(value: String) -> String {
+    let mut res = String::with_capacity(value.len());
+    let mut word_start = true;
+    for c in value.chars() {
+        if c.is_whitespace() || c == '_' || c == '-' {
+            word_start = true;
+            res.push(c);
+        } else if word_start {
+            word_start = false;
+            res.extend(c.to_uppercase());
+        } else {
+            res.extend(c.to_lowercase());
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_digits() {
+        assert_eq!(group_digits("1289347234".into(), ','), "1,289,347,234");
+        assert_eq!(group_digits("-123456".into(), ' '), "-123 456");
+        assert_eq!(group_digits("123".into(), ','), "123");
+        assert_eq!(group_digits("1234.5".into(), ','), "1234.5");
+        assert_eq!(group_digits("-".into(), ','), "-");
+        assert_eq!(group_digits("".into(), ','), "");
+    }
+
+    #[test]
+    fn test_humanize_duration() {
+        assert_eq!(humanize_duration(Duration::from_secs(8100)), "2h 15m");
+        assert_eq!(humanize_duration(Duration::from_secs(8105)), "2h 15m");
+        assert_eq!(humanize_duration(Duration::from_secs(7205)), "2h");
+        assert_eq!(humanize_duration(Duration::from_secs(273600)), "3d 4h");
+        assert_eq!(humanize_duration(Duration::from_secs(45)), "45s");
+        assert_eq!(humanize_duration(Duration::from_millis(250)), "250ms");
+        assert_eq!(format_duration(&90u32, true), "1m 30s");
+        assert_eq!(format_duration(&90u32, false), "90");
+        assert_eq!(format_duration(&-5i64, true), "-5");
+        assert_eq!(format_duration(&Duration::from_millis(1500), false), "1.5s");
+    }
+
+    #[test]
+    fn test_titlecase() {
+        assert_eq!(titlecase("SHUTOFF".into()), "Shutoff");
+        assert_eq!(
+            titlecase("power on-line_state".into()),
+            "Power On-Line_State"
+        );
+        assert_eq!(titlecase("".into()), "");
+    }
+
+    #[test]
+    fn test_humanize_bytes() {
+        assert_eq!(humanize_bytes("0".into()), "0 B");
+        assert_eq!(humanize_bytes("1023".into()), "1023 B");
+        assert_eq!(humanize_bytes("524288".into()), "512 KiB");
+        assert_eq!(humanize_bytes("1503238554".into()), "1.4 GiB");
+        assert_eq!(humanize_bytes(u64::MAX.to_string()), "16 EiB");
+        assert_eq!(humanize_bytes("-1".into()), "-1");
+        assert_eq!(humanize_bytes("n/a".into()), "n/a");
+    }
+}
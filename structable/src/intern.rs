@@ -0,0 +1,105 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deduplication of the repeated cell values
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Pool of the shared strings
+///
+/// Every distinct value is allocated only once and shared by all cells containing it. This
+/// reduces memory of the large listings repeating the same values (status, zone, flavor) many
+/// times while they are kept around (i.e. cached for the repeated rendering or diffing).
+#[derive(Clone, Debug, Default)]
+pub struct StringPool {
+    values: HashSet<Arc<str>>,
+}
+
+impl StringPool {
+    /// Create an empty pool
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the shared instance of the value, adding it to the pool when not yet present
+    pub fn intern<S: AsRef<str>>(&mut self, value: S) -> Arc<str> {
+        let value = value.as_ref();
+        if let Some(existing) = self.values.get(value) {
+            return existing.clone();
+        }
+        let shared: Arc<str> = Arc::from(value);
+        self.values.insert(shared.clone());
+        shared
+    }
+
+    /// Intern every cell of the row
+    pub fn intern_row(&mut self, row: Vec<String>) -> Vec<Arc<str>> {
+        row.into_iter().map(|cell| self.intern(cell)).collect()
+    }
+
+    /// Number of distinct values in the pool
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether the pool contains no values
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Convert the built list table into the table with deduplicated cells
+///
+/// Accepts the output of [`build_list_table`](crate::build_list_table) and consumes rows one by one
+/// so that the original strings are released as soon as they are interned. The peak memory while
+/// building the table is not reduced, only the memory of the retained table.
+pub fn intern_table(
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+) -> (Vec<Arc<str>>, Vec<Vec<Arc<str>>>) {
+    let mut pool = StringPool::new();
+    let headers = pool.intern_row(headers);
+    let rows = rows.into_iter().map(|row| pool.intern_row(row)).collect();
+    (headers, rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let mut pool = StringPool::new();
+        let a = pool.intern("active");
+        let b = pool.intern(String::from("active"));
+        let c = pool.intern("error");
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(pool.len(), 2);
+    }
+
+    #[test]
+    fn test_intern_table() {
+        let headers = vec!["Name".to_string(), "Status".to_string()];
+        let rows = vec![
+            vec!["a".to_string(), "active".to_string()],
+            vec!["b".to_string(), "active".to_string()],
+        ];
+        let (headers, rows) = intern_table(headers, rows);
+        assert_eq!(&*headers[1], "Status");
+        assert_eq!(&*rows[0][0], "a");
+        assert!(Arc::ptr_eq(&rows[0][1], &rows[1][1]));
+    }
+}
@@ -0,0 +1,478 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Localization of the column titles, headers and placeholders
+//!
+//! Texts are looked up with the [`StructTableOptions::localize`] using the `l10n_key` of the
+//! fields and the message keys defined here. With the `i18n` feature [`Localized`] options
+//! resolve them from the [Fluent](https://projectfluent.org) bundle:
+//!
+//! ```ftl
+//! user-id = Benutzer-ID
+//! structable-attribute = Attribut
+//! structable-value = Wert
+//! structable-more-rows = … und { $count } weitere
+//! ```
+
+use crate::{FieldInfo, StructTableOptions, REDACTED_PLACEHOLDER};
+
+/// Key of the `Attribute` header of the single structure table
+pub const ATTRIBUTE: &str = "structable-attribute";
+/// Key of the `Value` header of the single structure table
+pub const VALUE: &str = "structable-value";
+/// Key of the placeholder of the redacted values
+pub const REDACTED: &str = "structable-redacted";
+/// Key of the placeholder of the field data that failed to be serialized
+pub const SERIALIZATION_ERROR: &str = "structable-serialization-error";
+/// Key of the `Status` header of the summary table
+pub const STATUS: &str = "structable-status";
+/// Key of the `Count` header of the summary table
+pub const COUNT: &str = "structable-count";
+/// Key of the label of the entries without status in the summary table
+pub const NO_STATUS: &str = "structable-no-status";
+/// Key of the trailer row of the list table with rows omitted (`count` argument)
+pub const MORE_ROWS: &str = "structable-more-rows";
+
+/// Output title of the column
+///
+/// Renamed title takes precedence over the localized title of the field with the `l10n_key`.
+pub(crate) fn output_title<O: StructTableOptions>(
+    options: &O,
+    schema: &[FieldInfo],
+    title: &str,
+) -> String {
+    options
+        .column_title(title)
+        .or_else(|| {
+            schema
+                .iter()
+                .find(|info| info.title == title)
+                .and_then(|info| info.l10n_key.as_deref())
+                .and_then(|key| options.localize(key))
+        })
+        .unwrap_or_else(|| title.to_string())
+}
+
+/// Placeholder of the redacted values
+pub(crate) fn redacted_placeholder<O: StructTableOptions>(options: &O) -> String {
+    options
+        .localize(REDACTED)
+        .unwrap_or_else(|| String::from(REDACTED_PLACEHOLDER))
+}
+
+/// Text of the trailer row of the list table with `omitted` rows
+pub(crate) fn more_rows_trailer<O: StructTableOptions>(options: &O, omitted: usize) -> String {
+    options
+        .localize_args(MORE_ROWS, &[("count", &omitted.to_string())])
+        .unwrap_or_else(|| format!("… and {omitted} more"))
+}
+
+#[cfg(feature = "i18n")]
+pub use fluent::Localized;
+
+#[cfg(feature = "i18n")]
+mod fluent {
+    use std::borrow::Borrow;
+
+    use fluent_bundle::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+
+    use crate::{
+        DisplayTimezone, EllipsisPosition, NullPolicy, StructTableOptions,
+        SERIALIZATION_ERROR_PLACEHOLDER,
+    };
+
+    /// Options localizing the output with the Fluent bundle
+    ///
+    /// Every method of the [`StructTableOptions`] is delegated to the wrapped options. Messages
+    /// missing in the bundle are left untranslated.
+    pub struct Localized<'a, O, R> {
+        options: &'a O,
+        bundle: &'a FluentBundle<R>,
+    }
+
+    impl<'a, O, R> Localized<'a, O, R> {
+        /// Localize output of the `options` with the `bundle`
+        pub fn new(options: &'a O, bundle: &'a FluentBundle<R>) -> Self {
+            Self { options, bundle }
+        }
+    }
+
+    impl<O, R> StructTableOptions for Localized<'_, O, R>
+    where
+        O: StructTableOptions,
+        R: Borrow<FluentResource>,
+    {
+        fn wide_mode(&self) -> bool {
+            self.options.wide_mode()
+        }
+
+        fn pretty_mode(&self) -> bool {
+            self.options.pretty_mode()
+        }
+
+        fn should_return_field<S: AsRef<str>>(&self, field: S, is_wide_field: bool) -> bool {
+            self.options.should_return_field(field, is_wide_field)
+        }
+
+        fn should_return_column<S: AsRef<str>>(
+            &self,
+            title: S,
+            field_name: &str,
+            is_wide_field: bool,
+        ) -> bool {
+            self.options
+                .should_return_column(title, field_name, is_wide_field)
+        }
+
+        fn verbosity(&self) -> u8 {
+            self.options.verbosity()
+        }
+
+        fn should_return_column_level<S: AsRef<str>>(
+            &self,
+            title: S,
+            field_name: &str,
+            level: u8,
+        ) -> bool {
+            self.options
+                .should_return_column_level(title, field_name, level)
+        }
+
+        fn groups(&self) -> Vec<String> {
+            self.options.groups()
+        }
+
+        fn should_return_tagged_column<S: AsRef<str>>(
+            &self,
+            title: S,
+            field_name: &str,
+            level: u8,
+            tags: &[&str],
+        ) -> bool {
+            self.options
+                .should_return_tagged_column(title, field_name, level, tags)
+        }
+
+        fn field_data_json_pointer<S: AsRef<str>>(&self, field: S) -> Option<String> {
+            self.options.field_data_json_pointer(field)
+        }
+
+        fn field_data_jmespath<S: AsRef<str>>(&self, field: S) -> Option<String> {
+            self.options.field_data_jmespath(field)
+        }
+
+        fn column_max_width<S: AsRef<str>>(&self, field: S) -> Option<usize> {
+            self.options.column_max_width(field)
+        }
+
+        fn ellipsis_position<S: AsRef<str>>(&self, field: S) -> Option<EllipsisPosition> {
+            self.options.ellipsis_position(field)
+        }
+
+        fn hide_empty_columns(&self) -> bool {
+            self.options.hide_empty_columns()
+        }
+
+        fn max_rows(&self) -> Option<usize> {
+            self.options.max_rows()
+        }
+
+        fn head_rows(&self) -> Option<usize> {
+            self.options.head_rows()
+        }
+
+        fn tail_rows(&self) -> Option<usize> {
+            self.options.tail_rows()
+        }
+
+        /// Default placeholder is replaced with the localized one, custom placeholders are kept
+        fn serialization_error_placeholder<S: AsRef<str>>(
+            &self,
+            field: S,
+            error: &serde_json::Error,
+        ) -> String {
+            let placeholder = self.options.serialization_error_placeholder(field, error);
+            match placeholder == SERIALIZATION_ERROR_PLACEHOLDER {
+                true => self
+                    .localize(super::SERIALIZATION_ERROR)
+                    .unwrap_or(placeholder),
+                false => placeholder,
+            }
+        }
+
+        fn field_data_not_found<S: AsRef<str>>(&self, field: S, query: &str) {
+            self.options.field_data_not_found(field, query)
+        }
+
+        fn strict_mode(&self) -> bool {
+            self.options.strict_mode()
+        }
+
+        fn humanize_values(&self) -> bool {
+            self.options.humanize_values()
+        }
+
+        fn hyperlinks(&self) -> bool {
+            self.options.hyperlinks()
+        }
+
+        fn thousands_separator(&self) -> char {
+            self.options.thousands_separator()
+        }
+
+        fn null_policy(&self) -> NullPolicy {
+            self.options.null_policy()
+        }
+
+        fn display_timezone(&self) -> DisplayTimezone {
+            self.options.display_timezone()
+        }
+
+        fn column_title<S: AsRef<str>>(&self, field: S) -> Option<String> {
+            self.options.column_title(field)
+        }
+
+        fn redact_field<S: AsRef<str>>(&self, field: S) -> bool {
+            self.options.redact_field(field)
+        }
+
+        fn requested_fields(&self) -> Vec<String> {
+            self.options.requested_fields()
+        }
+
+        fn snapshot_mode(&self) -> bool {
+            self.options.snapshot_mode()
+        }
+
+        fn localize(&self, key: &str) -> Option<String> {
+            match self.bundle.get_message(key).and_then(|msg| msg.value()) {
+                Some(pattern) => {
+                    let mut errors = Vec::new();
+                    Some(
+                        self.bundle
+                            .format_pattern(pattern, None, &mut errors)
+                            .into_owned(),
+                    )
+                }
+                None => self.options.localize(key),
+            }
+        }
+
+        fn localize_args(&self, key: &str, args: &[(&str, &str)]) -> Option<String> {
+            match self.bundle.get_message(key).and_then(|msg| msg.value()) {
+                Some(pattern) => {
+                    let mut fluent_args = FluentArgs::new();
+                    for (name, value) in args {
+                        fluent_args.set(*name, FluentValue::try_number(value));
+                    }
+                    let mut errors = Vec::new();
+                    Some(
+                        self.bundle
+                            .format_pattern(pattern, Some(&fluent_args), &mut errors)
+                            .into_owned(),
+                    )
+                }
+                None => self.options.localize_args(key, args),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_list_table, build_summary_table, build_table, OutputConfig, StructTable};
+
+    #[derive(StructTable)]
+    struct User {
+        #[structable(title = "ID", l10n_key = "user-id")]
+        id: u32,
+        name: &'static str,
+    }
+
+    /// Options translating the messages with the lookup callback
+    struct German(OutputConfig);
+
+    impl StructTableOptions for German {
+        fn wide_mode(&self) -> bool {
+            self.0.wide_mode()
+        }
+
+        fn pretty_mode(&self) -> bool {
+            self.0.pretty_mode()
+        }
+
+        fn should_return_field<S: AsRef<str>>(&self, field: S, is_wide_field: bool) -> bool {
+            self.0.should_return_field(field, is_wide_field)
+        }
+
+        fn redact_field<S: AsRef<str>>(&self, field: S) -> bool {
+            self.0.redact_field(field)
+        }
+
+        fn column_title<S: AsRef<str>>(&self, field: S) -> Option<String> {
+            self.0.column_title(field)
+        }
+
+        fn localize(&self, key: &str) -> Option<String> {
+            match key {
+                "user-id" => Some("Benutzer-ID".into()),
+                ATTRIBUTE => Some("Attribut".into()),
+                VALUE => Some("Wert".into()),
+                REDACTED => Some("<geschwärzt>".into()),
+                STATUS => Some("Zustand".into()),
+                COUNT => Some("Anzahl".into()),
+                NO_STATUS => Some("(kein)".into()),
+                _ => None,
+            }
+        }
+
+        fn localize_args(&self, key: &str, args: &[(&str, &str)]) -> Option<String> {
+            match (key, args) {
+                (MORE_ROWS, [("count", count)]) => Some(format!("… und {count} weitere")),
+                _ => None,
+            }
+        }
+
+        fn max_rows(&self) -> Option<usize> {
+            self.0.max_rows()
+        }
+    }
+
+    #[test]
+    fn test_localize() {
+        let data = [User { id: 1, name: "a" }];
+        let options = German(OutputConfig {
+            redact_fields: ["name".to_string()].into(),
+            ..Default::default()
+        });
+        assert_eq!(
+            build_list_table(data.iter(), &options),
+            (
+                vec!["Benutzer-ID".to_string(), "name".to_string()],
+                vec![vec!["1".to_string(), "<geschwärzt>".to_string()]]
+            )
+        );
+        assert_eq!(
+            build_table(&data[0], &options),
+            (
+                vec!["Attribut".to_string(), "Wert".to_string()],
+                vec![
+                    vec!["Benutzer-ID".to_string(), "1".to_string()],
+                    vec!["name".to_string(), "<geschwärzt>".to_string()],
+                ]
+            )
+        );
+        let options = German(OutputConfig {
+            rename: [("id".to_string(), "Key".to_string())].into(),
+            ..Default::default()
+        });
+        assert_eq!(
+            build_list_table(data.iter(), &options).0,
+            vec!["Key", "name"]
+        );
+
+        #[derive(StructTable)]
+        struct Server {
+            #[structable(optional)]
+            status: Option<&'static str>,
+        }
+
+        let servers = [
+            Server {
+                status: Some("ACTIVE"),
+            },
+            Server { status: None },
+        ];
+        assert_eq!(
+            build_summary_table(servers.iter(), &options),
+            (
+                vec!["Zustand".to_string(), "Anzahl".to_string()],
+                vec![
+                    vec!["(kein)".to_string(), "1".to_string()],
+                    vec!["ACTIVE".to_string(), "1".to_string()],
+                ]
+            )
+        );
+
+        let data = [User { id: 1, name: "a" }, User { id: 2, name: "b" }];
+        let options = German(OutputConfig {
+            max_rows: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(
+            build_list_table(data.iter(), &options).1[1],
+            vec!["… und 1 weitere".to_string(), String::new()]
+        );
+        assert_eq!(
+            build_list_table(
+                data.iter(),
+                &OutputConfig {
+                    max_rows: Some(1),
+                    ..Default::default()
+                }
+            )
+            .1[1],
+            vec!["… and 1 more".to_string(), String::new()]
+        );
+    }
+
+    #[cfg(feature = "i18n")]
+    #[test]
+    fn test_fluent() {
+        use fluent_bundle::{FluentBundle, FluentResource};
+
+        let resource = FluentResource::try_new(
+            "user-id = Benutzer-ID\nstructable-attribute = Attribut\n\
+             structable-more-rows = { $count ->\n    [one] … und ein weiterer\n   *[other] … und { $count } weitere\n}\n"
+                .to_string(),
+        )
+        .unwrap();
+        let mut bundle = FluentBundle::new(vec!["de".parse().unwrap()]);
+        bundle.set_use_isolating(false);
+        bundle.add_resource(resource).unwrap();
+        let config = OutputConfig::default();
+        let options = Localized::new(&config, &bundle);
+        let data = User { id: 1, name: "a" };
+        assert_eq!(
+            build_table(&data, &options),
+            (
+                vec!["Attribut".to_string(), "Value".to_string()],
+                vec![
+                    vec!["Benutzer-ID".to_string(), "1".to_string()],
+                    vec!["name".to_string(), "a".to_string()],
+                ]
+            )
+        );
+
+        let config = OutputConfig {
+            max_rows: Some(1),
+            ..Default::default()
+        };
+        let options = Localized::new(&config, &bundle);
+        let data = [
+            User { id: 1, name: "a" },
+            User { id: 2, name: "b" },
+            User { id: 3, name: "c" },
+        ];
+        assert_eq!(
+            build_list_table(data.iter(), &options).1[1][0],
+            "… und 2 weitere"
+        );
+        assert_eq!(
+            build_list_table(data[..2].iter(), &options).1[1][0],
+            "… und ein weiterer"
+        );
+    }
+}
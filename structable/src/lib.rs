@@ -128,11 +128,226 @@
 //!
 //!  - `title` column name to be returned. When unset field name is used.
 //!
-//!  - `wide` return field only in the `wide` mode, or when explicitly requested through `fields`
+//!  - `wide` return field only in the `wide` mode, or when explicitly requested through `fields`.
+//!    Optionally with the verbosity level (i.e. `#[structable(wide = 2)]`) required to return the
+//!    field ([`StructTableOptions::verbosity`]), plain `wide` is the level `1`.
 //!
 //!  - `serialize` serialize field value to the json. When `pretty` mode is requested uses
 //!    `to_pretty_string()`
 //!
+//!  - `expand` (for the structure or JSON object values) render every nested key as the
+//!    separate `parent.child` row of the [`build_table`] instead of the single JSON cell. Field
+//!    is serialized (requires `Serialize`) and list tables keep the JSON.
+//!
+//!  - `serialize_with` function with the `serde` `serialize_with` signature (`fn<S: Serializer>(&T,
+//!    S) -> Result<S::Ok, S::Error>`) shaping the json of the field (i.e. stripping nulls) before
+//!    the (pretty) printing. Implies `serialize`. For `optional` fields receives the inner value.
+//!
+//!  - `brief` return field in the normal mode when the structure is `default_wide`
+//!
+//!  - `skip` never return the field (neither as the column nor as the status)
+//!
+//!  - `status` the field is the status of the row ([`StructTable::status`]), the field titled
+//!    `status` is used otherwise. With multiple status fields the first one is the primary and all
+//!    of them are returned by the [`StructTable::statuses`].
+//!
+//!  - `flatten` merge columns of the field which type also implements the [`StructTable`] into
+//!    the parent row (similarly to the `serde(flatten)`). With `optional` missing value results in
+//!    empty cells. Column index constants are only generated for the fields preceding the first
+//!    flattened one.
+//!  - `inner` delegate headers, data and status of the whole structure to the field which type
+//!    also implements the [`StructTable`] (i.e. for generic wrappers like `Paginated<T>`). Other
+//!    fields are ignored. Only one field can be marked as `inner`.
+//!  - `spread` turn the keys of the map field (i.e. `HashMap<String, V>`) into additional columns
+//!    sorted by the key. Headers then depend on the instance so `instance_headers` is implemented.
+//!    Values are rendered using `Display` unless `serialize` is set.
+//!
+//!  - `datetime_format` format of the timestamp field (`chrono::DateTime`, `chrono::NaiveDateTime`
+//!    with the `chrono` feature or `time::OffsetDateTime` with the `time` feature) converted to the
+//!    [`StructTableOptions::display_timezone`] (i.e. `#[structable(datetime_format = "%Y-%m-%d
+//!    %H:%M")]`). Any type implementing the [`FormatDateTime`] is supported.
+//!
+//!  - `getter` name of the structure method returning the field value instead of reading the
+//!    field directly (i.e. `#[structable(getter = "name")]`)
+//!
+//!  - `format_with` path of the function (`fn(&T) -> String`) rendering the field value instead of
+//!    the `to_string` (i.e. `#[structable(format_with = "format_size")]`). For `optional` fields
+//!    the function receives the inner value.
+//!
+//!  - `map` show label instead of the rendered value (i.e.
+//!    `#[structable(map(value = "1", to = "enabled"), map(value = "0", to = "disabled"))]`)
+//!
+//!  - `count` render the number of elements of the collection (anything with `len()`, i.e. `Vec`
+//!    or `HashMap`) instead of the content, optionally followed by the label (i.e.
+//!    `#[structable(count = "attachments")]` results in `3 attachments`). Full content (which for
+//!    maps requires `serialize`) is returned in the wide mode.
+//!
+//!  - `uppercase`, `lowercase` or `titlecase` normalize the case of the value (i.e. `SHUTOFF`
+//!    with `titlecase` results in `Shutoff`). Applied after the `map` and `bool_labels`.
+//!
+//!  - `bool_labels` labels of the boolean values given as `<true label>/<false label>` (i.e.
+//!    `#[structable(bool_labels = "enabled/disabled")]`)
+//!
+//!  - `thousands` insert separator (`StructTableOptions::thousands_separator`) between digit groups
+//!    of the integer value (`1289347234` => `1,289,347,234`)
+//!
+//!  - `unit` append the unit to the value (i.e. `#[structable(unit = "MB")]` renders `512 MB`)
+//!    unless [`StructTableOptions::humanize_values`] is disabled. The unit is exposed through the
+//!    [`StructTable::schema`] for the exports keeping raw numbers.
+//!
+//!  - `bytes` render the integer byte count with the binary unit (`1503238554` => `1.4 GiB`)
+//!    unless [`StructTableOptions::humanize_values`] is disabled
+//!
+//!  - `duration` render the integer number of seconds or the `std::time::Duration` (any type
+//!    implementing the [`DurationValue`]) as `2h 15m` unless
+//!    [`StructTableOptions::humanize_values`] is disabled
+//!
+//!  - `hide_if_empty` omit the column from the list table when it is empty in every row
+//!
+//!  - `priority` column priority exposed through the [`StructTable::schema`] for renderers
+//!    deciding which columns to keep under width pressure (lower is more important, `0` by default)
+//!
+//!  - `order` position of the column in the output (i.e. `#[structable(order = 1)]`). Fields with
+//!    the `order` are returned first (ascending), the rest keeps the declaration order. `COL_`
+//!    constants follow the output order.
+//!
+//!  - `key` mark the field as (part of) the identity of the row (exposed through the
+//!    [`StructTable::schema`] and [`StructTable::key_columns`])
+//!
+//!  - `example` example value of the field exposed through the [`StructTable::schema`] (i.e.
+//!    `#[structable(example = "ACTIVE")]`)
+//!
+//!  - `direction` text direction hint of the values (`"auto"`, `"ltr"` or `"rtl"`) exposed
+//!    through the [`StructTable::schema`]
+//!
+//!  - `l10n_key` key of the localized column title looked up with the
+//!    [`StructTableOptions::localize`] (i.e. `#[structable(l10n_key = "user-id")]`). Renamed
+//!    titles take precedence.
+//!
+//!  - `ellipsis` position of the ellipsis in the values truncated to the column width (`"end"`,
+//!    `"start"` or `"middle"`), i.e. `"start"` keeps the tail of the paths and identifiers.
+//!    [`StructTableOptions::ellipsis_position`] takes precedence.
+//!
+//!  - `align` horizontal alignment hint of the column values (`"left"`, `"right"` or
+//!    `"center"`) for the renderers ([`StructTable::column_alignments`]).
+//!
+//!  - `style` and `color` free-form style hints of the column values (i.e.
+//!    `#[structable(style = "bold", color = "red")]`) for the renderers
+//!    ([`StructTable::column_styles`]).
+//!
+//!  - `link` template of the hyperlink target of the value referencing other fields (i.e.
+//!    `#[structable(link = "https://dashboard/servers/{id}")]`, fields must implement
+//!    `Display`). Targets are returned by the [`StructTable::links`] and with the
+//!    [`StructTableOptions::hyperlinks`] the table builders wrap the (truncated) values into the
+//!    OSC 8 terminal hyperlinks. Tuple structure fields are referenced by their position (`{0}`).
+//!
+//!  - `max_length` truncate longer values with the ellipsis (i.e. `#[structable(max_length = 60)]`)
+//!    unless the wide or pretty mode is requested. [`StructTableOptions::column_max_width`] takes
+//!    precedence.
+//!
+//!  - `default_value` placeholder shown instead of the missing value of the `optional` (or
+//!    `optional_default`) field, so that it is also returned in the single structure table (i.e.
+//!    `#[structable(optional, default_value = "-")]`)
+//!
+//!  - `optional_default` treat the value equal to `Default::default()` (empty string, `0`) as
+//!    missing, similarly to the `None` of the `optional` field. Requires `Default + PartialEq`.
+//!
+//!  - `skip_if_empty` treat the empty value (empty string, collection or JSON object) as missing.
+//!    Such values are omitted from the single structure table and rendered as the placeholder
+//!    in the list table. Empty collections and objects (`[]`, `{}`) are only recognized in the
+//!    serialized values.
+//!  - `tags` comma separated groups of the field (i.e. `#[structable(tags = "network,detail")]`).
+//!    When [`StructTableOptions::groups`] are selected (`fields=@network` with the
+//!    [`OutputConfig`] parsed from the string) only the tagged fields and the explicitly requested
+//!    ones are returned.
+//!
+//!  - `join` separator of the joined elements (i.e. `#[structable(join = " | ")]`). Fields of any
+//!    other iterable type (`BTreeSet<T>`, `HashSet<T>`, etc.) with `join` are joined as well.
+//!    Takes precedence over the `serialize`.
+//!
+//!  Fields of the `Vec<T>`, `Vec<Option<T>>` and `Option<Vec<T>>` types are rendered by joining
+//!  the present elements with `, ` (or the `join` separator) unless `serialize` (or `pretty`) is
+//!  set.
+//!
+//!  For every field the `COL_<FIELD>` constant with the index of the column in the unfiltered
+//!  row (all fields returned) is generated (not for the `remote` types).
+//!
+//!  Doc comments of the fields are exposed as the column descriptions through the
+//!  [`StructTable::schema`] and [`StructTable::field_descriptions`] (i.e. for the `--help` of the
+//!  CLI field selection).
+//!
+//!  ## Structure parameters
+//!
+//!  - `default_wide` all fields are returned only in the `wide` mode unless marked as `brief`.
+//!
+//!  - `dynamic` headers depend on the instance (`instance_headers` is implemented instead of
+//!    `class_headers`). `optional` fields without value are not returned at all.
+//!
+//!  - `remote` implement the trait for the foreign type with the same (public) fields instead of
+//!    the annotated mirror structure (i.e. `#[structable(remote = "other::Type")]`).
+//!
+//!  - `compute` column computed by the method of the structure (`fn(&self) -> impl ToString`)
+//!    appended after the fields, i.e. `#[structable(compute(title = "Usage", method = "usage"))]`.
+//!    Optionally `wide`. Can be repeated.
+//!
+//!  - `use_serde_attrs` fields marked with `#[serde(skip)]` or `#[serde(skip_serializing)]` are
+//!    not returned and `#[serde(rename = "...")]` is the default title of the field (`title`
+//!    takes precedence).
+//!
+//!  ## Tuple structures
+//!
+//!  Fields of the tuple structures are titled with their position (`0`, `1`, ...) unless `title`
+//!  is set. Newtypes (single field tuple structures) are transparent and delegate to the
+//!  [`StructTable`] implementation of the inner type.
+//!
+//!  ```rust
+//!  # use structable::{StructTable, StructTableOptions};
+//!  #[derive(StructTable)]
+//!  struct Pair(#[structable(title = "Key")] String, u32);
+//!  ```
+//!
+//!  ## Enums
+//!
+//!  Enums are supported as well. Unit variants are rendered as their name in the column titled
+//!  with the enum name, newtype variants delegate to the [`StructTable`] implementation of the
+//!  inner type and struct (or tuple) variants render their fields (with the same field parameters
+//!  as the structures). Columns depend on the variant, so `instance_headers` is implemented and
+//!  rows of the different variants are merged in the list table.
+//!
+//!  ```rust
+//!  # use structable::{StructTable, StructTableOptions};
+//!  #[derive(StructTable)]
+//!  enum Event {
+//!      Started,
+//!      Stopped { code: i32 },
+//!  }
+//!  ```
+//!
+//!  ## Crate features
+//!
+//!  - `jmespath` support JMESPath expressions for extracting data of the `serialize` fields
+//!    ([`StructTableOptions::field_data_jmespath`]).
+//!
+//!  - `template` render list table rows using the `minijinja` templates ([`render_template`]).
+//!
+//!  - `unicase` match field titles using the Unicode case folding instead of the lowercase
+//!    comparison (i.e. `straße` matches `STRASSE`).
+//!
+//!  - `indicatif` use the `indicatif::ProgressBar` as the [`ProgressSink`] of the
+//!    [`build_list_table_with_progress`].
+//!
+//!  - `tracing` instrument table building with the `tracing` spans and debug events.
+//!
+//!  - `chrono` formatting of the `chrono` timestamps with the `datetime_format` field attribute.
+//!
+//!  - `time` formatting of the `time` timestamps with the `datetime_format` field attribute.
+//!
+//!  - `i18n` localization of the output with the [Fluent](https://projectfluent.org) bundle
+//!    ([`l10n::Localized`] options).
+//!
+//!  - `testing` helpers for testing of the built tables ([`assert_list_table_eq`] and
+//!    [`assert_table_eq`] macros).
+//!
 //!
 //! ## Example
 //!
@@ -182,7 +397,8 @@
 //! let config = OutputConfig {
 //!     fields: BTreeSet::from(["Last Name".to_string()]),
 //!     wide: false,
-//!     pretty: false
+//!     pretty: false,
+//!     ..Default::default()
 //! };
 //!
 //! let data = build_table(&user, &config);
@@ -198,10 +414,58 @@
 //! ```
 //!
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeSet;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 
 pub use structable_derive::StructTable;
 
+mod bidi;
+mod config;
+mod datetime;
+mod dynamic;
+mod error;
+mod format;
+mod grouped;
+mod humanize;
+mod intern;
+pub mod l10n;
+mod progress;
+mod set;
+mod spec;
+mod stats;
+mod table;
+#[cfg(feature = "template")]
+mod template;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod view;
+
+use bidi::truncate_value;
+pub use bidi::{contains_rtl, EllipsisPosition, TextDirection};
+pub use datetime::{DisplayTimezone, FormatDateTime};
+pub use error::{StructTableError, TableIssue, ValidationError, Warning};
+pub use format::{
+    format_csv, format_grouped_csv, format_grouped_html, format_grouped_markdown, format_html,
+    format_key_value_rows, format_linear, format_markdown, hyperlink, CsvOptions, CsvQuoteStyle,
+    HtmlOptions, NullPolicy,
+};
+pub use grouped::{build_grouped_table, GroupedTable};
+#[doc(hidden)]
+pub use humanize::format_duration;
+pub use humanize::{group_digits, humanize_bytes, humanize_duration, titlecase, DurationValue};
+pub use intern::{intern_table, StringPool};
+pub use progress::{build_list_table_with_progress, ProgressSink};
+pub use set::{build_table_set, TableSet};
+pub use spec::{ColumnFormat, ColumnSpec, SpecItem, TableSpec};
+pub use stats::{column_stats, ColumnStats};
+pub use table::Table;
+#[cfg(feature = "template")]
+pub use template::{render_template, TemplateError};
+pub use view::SelectedView;
+
+// Allow code generated by the derive macro to refer to `::structable` within this crate
+extern crate self as structable;
+
 /// Output configuration
 ///
 /// This structure is controlling how the table table is being built for a structure.
@@ -210,12 +474,73 @@ pub struct OutputConfig {
     /// Limit fields (their titles) to be returned
     #[serde(default)]
     pub fields: BTreeSet<String>,
+    /// Additional fields (their titles) to be returned together with the default ones. Unlike
+    /// `fields` it does not limit other columns and un-hides the `wide` fields.
+    #[serde(default)]
+    pub extra_fields: BTreeSet<String>,
+    /// Limit fields to the ones tagged with any of the groups (together with the `fields`)
+    #[serde(default)]
+    pub groups: BTreeSet<String>,
     /// Wide mode (additional fields requested)
     #[serde(default)]
     pub wide: bool,
+    /// Level of details (`wide = N` fields up to the level are returned, `1` is the wide mode)
+    #[serde(default)]
+    pub verbosity: u8,
     /// Pretty-print
     #[serde(default)]
     pub pretty: bool,
+    /// Maximum width (in characters) of the individual columns (by their titles)
+    #[serde(default)]
+    pub column_widths: HashMap<String, usize>,
+    /// Omit columns without data in every row of the list table
+    #[serde(default)]
+    pub hide_empty_columns: bool,
+    /// Maximum number of rows in the list table
+    #[serde(default)]
+    pub max_rows: Option<usize>,
+    /// Select only the first N entries of the list
+    #[serde(default)]
+    pub head: Option<usize>,
+    /// Select only the last N entries of the list (applied after the `head`)
+    #[serde(default)]
+    pub tail: Option<usize>,
+    /// Placeholder for the field data that failed to be serialized
+    #[serde(default)]
+    pub serialization_error_placeholder: Option<String>,
+    /// Fail building the table with `try_build_*` functions on data errors
+    #[serde(default)]
+    pub strict: bool,
+    /// Keep the raw values of the fields marked with the humanizing attributes (`bytes`,
+    /// `duration`, `unit`)
+    #[serde(default)]
+    pub raw_values: bool,
+    /// Separator of the digit groups for the fields marked with `thousands` (`,` when unset)
+    #[serde(default)]
+    pub thousands_separator: Option<char>,
+    /// Representation of the missing values per output format
+    #[serde(default)]
+    pub null_policy: NullPolicy,
+    /// Timezone the fields with the `datetime_format` are converted to
+    #[serde(default)]
+    pub display_timezone: DisplayTimezone,
+    /// Output titles of the columns (by their field titles)
+    #[serde(default)]
+    pub rename: HashMap<String, String>,
+    /// Fields (their titles) which values are masked on output
+    #[serde(default)]
+    pub redact_fields: BTreeSet<String>,
+    /// Deterministic output for the snapshot tests (sorted keys of the serialized objects,
+    /// default separators, placeholders and timezone)
+    #[serde(default)]
+    pub snapshot: bool,
+    /// Position of the ellipsis in the truncated values of all columns (overrides the field
+    /// `ellipsis` attribute)
+    #[serde(default)]
+    pub ellipsis: Option<EllipsisPosition>,
+    /// Wrap values of the fields with the `link` into the OSC 8 terminal hyperlinks
+    #[serde(default)]
+    pub hyperlinks: bool,
 }
 
 /// StructTable output configuration trait
@@ -231,16 +556,232 @@ pub trait StructTableOptions {
     /// Whether the attribute should be returned
     fn should_return_field<S: AsRef<str>>(&self, field: S, is_wide_field: bool) -> bool;
 
+    /// Whether the column should be returned
+    ///
+    /// Receives the column title, the name of the structure field (equal to the title for columns
+    /// not backed by a field) and whether the column is `wide`. Delegates to the
+    /// [`should_return_field`](StructTableOptions::should_return_field) by default.
+    fn should_return_column<S: AsRef<str>>(
+        &self,
+        title: S,
+        _field_name: &str,
+        is_wide_field: bool,
+    ) -> bool {
+        self.should_return_field(title, is_wide_field)
+    }
+
+    /// Level of details of the output
+    ///
+    /// Fields marked with `wide = N` are returned when the verbosity reaches `N`. Defaults to `1`
+    /// in the wide mode and `0` otherwise.
+    fn verbosity(&self) -> u8 {
+        u8::from(self.wide_mode())
+    }
+
+    /// Whether the column of the verbosity `level` should be returned
+    ///
+    /// Columns above the wide level (`1`) additionally require the sufficient
+    /// [`verbosity`](StructTableOptions::verbosity) unless explicitly requested. Delegates to the
+    /// [`should_return_column`](StructTableOptions::should_return_column) otherwise.
+    fn should_return_column_level<S: AsRef<str>>(
+        &self,
+        title: S,
+        field_name: &str,
+        level: u8,
+    ) -> bool {
+        if level > 1
+            && self.verbosity() < level
+            && !self
+                .requested_fields()
+                .iter()
+                .any(|x| titles_match(x, title.as_ref()))
+        {
+            return false;
+        }
+        self.should_return_column(title, field_name, level > 0)
+    }
+
+    /// Groups (field `tags`) of the columns to be returned
+    fn groups(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Whether the column of the verbosity `level` tagged with `tags` should be returned
+    ///
+    /// With [`groups`](StructTableOptions::groups) selected only columns tagged with any of them
+    /// or explicitly requested are returned. Delegates to the
+    /// [`should_return_column_level`](StructTableOptions::should_return_column_level) otherwise.
+    fn should_return_tagged_column<S: AsRef<str>>(
+        &self,
+        title: S,
+        field_name: &str,
+        level: u8,
+        tags: &[&str],
+    ) -> bool {
+        let groups = self.groups();
+        if groups.is_empty() {
+            return self.should_return_column_level(title, field_name, level);
+        }
+        tags.iter()
+            .any(|tag| groups.iter().any(|x| titles_match(x, tag)))
+            || self
+                .requested_fields()
+                .iter()
+                .any(|x| titles_match(x, title.as_ref()))
+    }
+
     /// Return json pointer for the attribute to extract the data during table build
     /// [RFC](https://datatracker.ietf.org/doc/html/rfc6901)
     fn field_data_json_pointer<S: AsRef<str>>(&self, _field: S) -> Option<String> {
         None
     }
+
+    /// Return [JMESPath](https://jmespath.org) expression for the attribute to extract the data
+    /// during table build. Applied after the json pointer and only when the `jmespath` feature is
+    /// enabled.
+    fn field_data_jmespath<S: AsRef<str>>(&self, _field: S) -> Option<String> {
+        None
+    }
+
+    /// Maximum width of the column value. Longer values are truncated with the `…`
+    fn column_max_width<S: AsRef<str>>(&self, _field: S) -> Option<usize> {
+        None
+    }
+
+    /// Position of the ellipsis in the truncated values of the column
+    ///
+    /// When `None` the position given with the field `ellipsis` attribute is used.
+    fn ellipsis_position<S: AsRef<str>>(&self, _field: S) -> Option<EllipsisPosition> {
+        None
+    }
+
+    /// Whether to omit list table columns without data in every row
+    fn hide_empty_columns(&self) -> bool {
+        false
+    }
+
+    /// Maximum number of rows in the list table. Omitted rows are indicated with the trailer row
+    /// (localized with the [`l10n::MORE_ROWS`] key)
+    fn max_rows(&self) -> Option<usize> {
+        None
+    }
+
+    /// Number of entries from the beginning of the list to build the table of
+    fn head_rows(&self) -> Option<usize> {
+        None
+    }
+
+    /// Number of entries from the end of the list to build the table of
+    fn tail_rows(&self) -> Option<usize> {
+        None
+    }
+
+    /// Return placeholder for the field data that failed to be serialized
+    ///
+    /// Implementations may additionally log or collect the underlying error.
+    fn serialization_error_placeholder<S: AsRef<str>>(
+        &self,
+        _field: S,
+        _error: &serde_json::Error,
+    ) -> String {
+        #[cfg(feature = "tracing")]
+        trace_serialization_error(_field.as_ref(), _error);
+        self.localize(l10n::SERIALIZATION_ERROR)
+            .unwrap_or_else(|| String::from(SERIALIZATION_ERROR_PLACEHOLDER))
+    }
+
+    /// Notification about the json pointer (or JMESPath expression) that does not resolve data
+    /// of the field
+    fn field_data_not_found<S: AsRef<str>>(&self, _field: S, _query: &str) {}
+
+    /// Whether the `try_build_*` functions fail on the data errors (serialization errors,
+    /// unresolved json pointers) instead of returning placeholders
+    fn strict_mode(&self) -> bool {
+        false
+    }
+
+    /// Whether the values of the fields marked with the humanizing attributes (`bytes`,
+    /// `duration`, `unit`) are rendered human friendly
+    fn humanize_values(&self) -> bool {
+        true
+    }
+
+    /// Separator of the digit groups for the fields marked with `thousands`
+    fn thousands_separator(&self) -> char {
+        ','
+    }
+
+    /// Representation of the missing values in the list table depending on the output format
+    fn null_policy(&self) -> NullPolicy {
+        NullPolicy::default()
+    }
+
+    /// Timezone the fields with the `datetime_format` are converted to
+    fn display_timezone(&self) -> DisplayTimezone {
+        DisplayTimezone::Original
+    }
+
+    /// Whether values of the fields with the `link` are wrapped into the OSC 8 terminal
+    /// hyperlinks
+    ///
+    /// Escape sequences are not accounted in the column widths. Link targets are always
+    /// available through the [`StructTable::links`].
+    fn hyperlinks(&self) -> bool {
+        false
+    }
+
+    /// Title to show instead of the field title on output
+    ///
+    /// Field matching (`fields`, column widths, etc.) always uses the original title.
+    fn column_title<S: AsRef<str>>(&self, _field: S) -> Option<String> {
+        None
+    }
+
+    /// Whether the value of the field must be masked on output
+    fn redact_field<S: AsRef<str>>(&self, _field: S) -> bool {
+        false
+    }
+
+    /// Fields explicitly requested by the user
+    ///
+    /// Used to report requested fields not matching any column.
+    fn requested_fields(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Whether the output must be fully deterministic (i.e. for the snapshot tests)
+    ///
+    /// Keys of the serialized objects are sorted independently of the `serde_json` map
+    /// implementation.
+    fn snapshot_mode(&self) -> bool {
+        false
+    }
+
+    /// Localized text of the message with the given key
+    ///
+    /// Used for the fields with the `l10n_key`, the `Attribute`/`Value` headers and the
+    /// placeholders (see the [`l10n`] module for the keys). With the `i18n` feature the
+    /// [`l10n::Localized`] options look the messages up in the Fluent bundle.
+    fn localize(&self, _key: &str) -> Option<String> {
+        None
+    }
+
+    /// Localized text of the message with the given key and the named arguments
+    ///
+    /// Used for the messages with variable parts (i.e. the `count` of the
+    /// [`l10n::MORE_ROWS`] trailer). Messages are left untranslated by default.
+    fn localize_args(&self, _key: &str, _args: &[(&str, &str)]) -> Option<String> {
+        None
+    }
 }
 
 impl StructTableOptions for OutputConfig {
     fn wide_mode(&self) -> bool {
-        self.wide
+        self.wide || self.verbosity > 0
+    }
+
+    fn verbosity(&self) -> u8 {
+        self.verbosity.max(u8::from(self.wide))
     }
 
     fn pretty_mode(&self) -> bool {
@@ -248,586 +789,4688 @@ impl StructTableOptions for OutputConfig {
     }
 
     fn should_return_field<S: AsRef<str>>(&self, field: S, is_wide_field: bool) -> bool {
-        if !is_wide_field {
-            self.fields.is_empty()
-                || self
-                    .fields
-                    .iter()
-                    .any(|x| x.to_lowercase() == field.as_ref().to_lowercase())
+        if self
+            .extra_fields
+            .iter()
+            .any(|x| titles_match(x, field.as_ref()))
+        {
+            true
+        } else if !is_wide_field {
+            self.fields.is_empty() || self.fields.iter().any(|x| titles_match(x, field.as_ref()))
         } else {
             (self.fields.is_empty() && self.wide_mode())
-                || self
-                    .fields
-                    .iter()
-                    .any(|x| x.to_lowercase() == field.as_ref().to_lowercase())
+                || self.fields.iter().any(|x| titles_match(x, field.as_ref()))
         }
     }
-}
 
-/// Trait for building tables out of structures
-pub trait StructTable {
-    /// Return Vector of table headers (attribute titles to be returned) that are not instance
-    /// specific (i.e. struct)
-    fn class_headers<O: StructTableOptions>(_config: &O) -> Option<Vec<String>> {
-        None
+    fn column_max_width<S: AsRef<str>>(&self, field: S) -> Option<usize> {
+        self.column_widths
+            .iter()
+            .find(|(k, _)| titles_match(k, field.as_ref()))
+            .map(|(_, v)| *v)
     }
 
-    /// Return Vector of table headers (attribute titles to be returned) from the instance that are
-    /// instance specific (i.e. HashMap)
-    fn instance_headers<O: StructTableOptions>(&self, _config: &O) -> Option<Vec<String>> {
-        None
+    fn ellipsis_position<S: AsRef<str>>(&self, _field: S) -> Option<EllipsisPosition> {
+        self.ellipsis
     }
 
-    /// Return vector of selected fields as `Option<String>`
-    fn data<O: StructTableOptions>(&self, config: &O) -> Vec<Option<String>>;
+    fn hide_empty_columns(&self) -> bool {
+        self.hide_empty_columns
+    }
 
-    /// Return structure status property
-    fn status(&self) -> Option<String> {
-        None
+    fn max_rows(&self) -> Option<usize> {
+        self.max_rows
     }
-}
 
-/// Build a table for a single structure
-///
-/// Returns a vector with first row being column headers ["Attribute", "Value"]. All other rows
-/// represent transposed table with first value in the vector being an attribute name and second
-/// value being the value itself. The optional attribute, which is `None` is not being returned.
-pub fn build_table<T, O>(data: &T, options: &O) -> (Vec<String>, Vec<Vec<String>>)
-where
-    T: StructTable,
-    O: StructTableOptions,
-{
-    let headers = Vec::from(["Attribute".into(), "Value".into()]);
-    let mut rows: Vec<Vec<String>> = Vec::new();
-    let col_headers = T::class_headers(options).or_else(|| data.instance_headers(options));
-    if let Some(hdr) = col_headers {
-        for (a, v) in hdr.iter().zip(data.data(options).iter()) {
-            if let Some(data) = v {
-                rows.push(Vec::from([a.to_string(), data.to_string()]));
-            }
+    fn head_rows(&self) -> Option<usize> {
+        self.head
+    }
+
+    fn tail_rows(&self) -> Option<usize> {
+        self.tail
+    }
+
+    fn serialization_error_placeholder<S: AsRef<str>>(
+        &self,
+        _field: S,
+        _error: &serde_json::Error,
+    ) -> String {
+        #[cfg(feature = "tracing")]
+        trace_serialization_error(_field.as_ref(), _error);
+        match &self.serialization_error_placeholder {
+            Some(placeholder) if !self.snapshot => placeholder.clone(),
+            _ => String::from(SERIALIZATION_ERROR_PLACEHOLDER),
         }
     }
-    (headers, rows)
-}
 
-/// Build a table for list of entries
-///
-/// Returns vector of vector of strings with first row being table headers and all other rows are
-/// the values themselves.
-pub fn build_list_table<I, T, O>(data: I, options: &O) -> (Vec<String>, Vec<Vec<String>>)
-where
-    I: Iterator<Item = T>,
-    T: StructTable,
-    O: StructTableOptions,
-{
-    if let Some(headers) = T::class_headers(options) {
-        let rows: Vec<Vec<String>> = Vec::from_iter(data.map(|item| {
-            item.data(options)
-                .into_iter()
-                .map(|el| el.unwrap_or_else(|| String::from(" ")))
-                .collect::<Vec<String>>()
-        }));
-        (headers, rows)
-    } else {
-        // TODO: Make method returning result
-        (Vec::new(), Vec::new())
+    fn strict_mode(&self) -> bool {
+        self.strict
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use serde_json::{json, Value};
-    use std::collections::BTreeMap;
+    fn humanize_values(&self) -> bool {
+        !self.raw_values
+    }
 
-    use super::*;
+    fn thousands_separator(&self) -> char {
+        match self.snapshot {
+            true => ',',
+            false => self.thousands_separator.unwrap_or(','),
+        }
+    }
 
-    #[derive(Default, Deserialize, Serialize, StructTable)]
-    struct User {
-        #[structable(title = "ID")]
-        id: u64,
-        first_name: String,
-        last_name: String,
-        #[structable(title = "Long", wide)]
-        extra: String,
-        #[structable(optional, serialize, wide)]
-        complex_data: Option<Value>,
-        #[structable(optional)]
-        dummy: Option<String>,
+    fn null_policy(&self) -> NullPolicy {
+        match self.snapshot {
+            true => NullPolicy::default(),
+            false => self.null_policy.clone(),
+        }
     }
 
-    #[derive(Deserialize, Serialize, StructTable)]
-    struct StatusStruct {
-        #[structable(status)]
-        status: String,
+    fn display_timezone(&self) -> DisplayTimezone {
+        match self.snapshot {
+            true => DisplayTimezone::Original,
+            false => self.display_timezone,
+        }
     }
 
-    #[derive(Clone, Deserialize, Serialize)]
-    enum Status {
-        Dummy,
+    fn hyperlinks(&self) -> bool {
+        self.hyperlinks && !self.snapshot
     }
 
-    #[derive(Deserialize, Serialize, StructTable)]
-    struct SerializeStatusStruct {
-        #[structable(serialize, status)]
-        status: Status,
+    fn column_title<S: AsRef<str>>(&self, field: S) -> Option<String> {
+        self.rename
+            .iter()
+            .find(|(k, _)| titles_match(k, field.as_ref()))
+            .map(|(_, v)| v.clone())
     }
 
-    #[derive(Deserialize, Serialize, StructTable)]
-    struct SerializeOptionStatusStruct {
-        #[structable(optional, serialize, status)]
-        status: Option<Status>,
+    fn redact_field<S: AsRef<str>>(&self, field: S) -> bool {
+        self.redact_fields
+            .iter()
+            .any(|x| titles_match(x, field.as_ref()))
     }
 
-    #[derive(Deserialize, Serialize, StructTable)]
-    struct OptionStatusStruct {
-        #[structable(status, optional)]
-        status: Option<String>,
+    fn requested_fields(&self) -> Vec<String> {
+        self.fields
+            .iter()
+            .chain(self.extra_fields.iter())
+            .cloned()
+            .collect()
     }
 
-    #[test]
-    fn test_single() {
-        let config = OutputConfig::default();
-        let user = User {
-            id: 1,
-            first_name: "Scooby".into(),
-            last_name: "Doo".into(),
-            extra: "XYZ".into(),
-            complex_data: Some(json!({"a": "b", "c": "d"})),
-            dummy: None,
-        };
-        assert_eq!(
-            build_table(&user, &config),
+    fn groups(&self) -> Vec<String> {
+        self.groups.iter().cloned().collect()
+    }
+
+    fn snapshot_mode(&self) -> bool {
+        self.snapshot
+    }
+}
+
+/// Case insensitive comparison of the field titles
+///
+/// Uses the Unicode case folding with the `unicase` feature and lowercase comparison otherwise.
+pub fn titles_match(a: &str, b: &str) -> bool {
+    #[cfg(feature = "unicase")]
+    {
+        unicase::UniCase::new(a) == unicase::UniCase::new(b)
+    }
+    #[cfg(not(feature = "unicase"))]
+    {
+        a.to_lowercase() == b.to_lowercase()
+    }
+}
+
+/// Options wrapper recording the data errors reported during the table build
+///
+/// Every method of the [`StructTableOptions`] must be delegated to the wrapped options.
+struct ErrorCollector<'a, O> {
+    options: &'a O,
+    errors: RefCell<Vec<StructTableError>>,
+}
+
+impl<'a, O: StructTableOptions> ErrorCollector<'a, O> {
+    fn new(options: &'a O) -> Self {
+        Self {
+            options,
+            errors: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Return all recorded errors
+    fn into_errors(self) -> Vec<StructTableError> {
+        self.errors.into_inner()
+    }
+
+    /// Return the first recorded error
+    fn into_result<T>(self, value: T) -> Result<T, StructTableError> {
+        match self.errors.into_inner().into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(value),
+        }
+    }
+}
+
+impl<O: StructTableOptions> StructTableOptions for ErrorCollector<'_, O> {
+    fn wide_mode(&self) -> bool {
+        self.options.wide_mode()
+    }
+
+    fn pretty_mode(&self) -> bool {
+        self.options.pretty_mode()
+    }
+
+    fn should_return_field<S: AsRef<str>>(&self, field: S, is_wide_field: bool) -> bool {
+        self.options.should_return_field(field, is_wide_field)
+    }
+
+    fn should_return_column<S: AsRef<str>>(
+        &self,
+        title: S,
+        field_name: &str,
+        is_wide_field: bool,
+    ) -> bool {
+        self.options
+            .should_return_column(title, field_name, is_wide_field)
+    }
+
+    fn verbosity(&self) -> u8 {
+        self.options.verbosity()
+    }
+
+    fn should_return_column_level<S: AsRef<str>>(
+        &self,
+        title: S,
+        field_name: &str,
+        level: u8,
+    ) -> bool {
+        self.options
+            .should_return_column_level(title, field_name, level)
+    }
+
+    fn groups(&self) -> Vec<String> {
+        self.options.groups()
+    }
+
+    fn should_return_tagged_column<S: AsRef<str>>(
+        &self,
+        title: S,
+        field_name: &str,
+        level: u8,
+        tags: &[&str],
+    ) -> bool {
+        self.options
+            .should_return_tagged_column(title, field_name, level, tags)
+    }
+
+    fn field_data_json_pointer<S: AsRef<str>>(&self, field: S) -> Option<String> {
+        self.options.field_data_json_pointer(field)
+    }
+
+    fn field_data_jmespath<S: AsRef<str>>(&self, field: S) -> Option<String> {
+        self.options.field_data_jmespath(field)
+    }
+
+    fn column_max_width<S: AsRef<str>>(&self, field: S) -> Option<usize> {
+        self.options.column_max_width(field)
+    }
+
+    fn ellipsis_position<S: AsRef<str>>(&self, field: S) -> Option<EllipsisPosition> {
+        self.options.ellipsis_position(field)
+    }
+
+    fn hide_empty_columns(&self) -> bool {
+        self.options.hide_empty_columns()
+    }
+
+    fn max_rows(&self) -> Option<usize> {
+        self.options.max_rows()
+    }
+
+    fn head_rows(&self) -> Option<usize> {
+        self.options.head_rows()
+    }
+
+    fn tail_rows(&self) -> Option<usize> {
+        self.options.tail_rows()
+    }
+
+    fn serialization_error_placeholder<S: AsRef<str>>(
+        &self,
+        field: S,
+        error: &serde_json::Error,
+    ) -> String {
+        self.errors
+            .borrow_mut()
+            .push(StructTableError::Serialization {
+                field: field.as_ref().to_string(),
+                message: error.to_string(),
+            });
+        self.options.serialization_error_placeholder(field, error)
+    }
+
+    fn field_data_not_found<S: AsRef<str>>(&self, field: S, query: &str) {
+        self.errors
+            .borrow_mut()
+            .push(StructTableError::DataNotFound {
+                field: field.as_ref().to_string(),
+                query: query.to_string(),
+            });
+        self.options.field_data_not_found(field, query)
+    }
+
+    fn strict_mode(&self) -> bool {
+        self.options.strict_mode()
+    }
+
+    fn humanize_values(&self) -> bool {
+        self.options.humanize_values()
+    }
+
+    fn hyperlinks(&self) -> bool {
+        self.options.hyperlinks()
+    }
+
+    fn thousands_separator(&self) -> char {
+        self.options.thousands_separator()
+    }
+
+    fn null_policy(&self) -> NullPolicy {
+        self.options.null_policy()
+    }
+
+    fn display_timezone(&self) -> DisplayTimezone {
+        self.options.display_timezone()
+    }
+
+    fn column_title<S: AsRef<str>>(&self, field: S) -> Option<String> {
+        self.options.column_title(field)
+    }
+
+    fn redact_field<S: AsRef<str>>(&self, field: S) -> bool {
+        self.options.redact_field(field)
+    }
+
+    fn requested_fields(&self) -> Vec<String> {
+        self.options.requested_fields()
+    }
+
+    fn snapshot_mode(&self) -> bool {
+        self.options.snapshot_mode()
+    }
+
+    fn localize(&self, key: &str) -> Option<String> {
+        self.options.localize(key)
+    }
+
+    fn localize_args(&self, key: &str, args: &[(&str, &str)]) -> Option<String> {
+        self.options.localize_args(key, args)
+    }
+}
+
+/// Extract the data of the serialized field according to the options
+///
+/// Used by the derived code. Unresolved json pointers and failed JMESPath queries result in
+/// `Value::Null`.
+#[doc(hidden)]
+pub fn extract_field_data<O: StructTableOptions>(
+    options: &O,
+    field: &str,
+    mut value: serde_json::Value,
+) -> serde_json::Value {
+    if let Some(jp) = options.field_data_json_pointer(field) {
+        value = match value.pointer_mut(jp.as_ref()) {
+            Some(val) => val.take(),
+            None => {
+                options.field_data_not_found(field, &jp);
+                serde_json::Value::Null
+            }
+        };
+    }
+    #[cfg(feature = "jmespath")]
+    if let Some(expr) = options.field_data_jmespath(field) {
+        value = match jmespath::compile(&expr)
+            .and_then(|compiled| compiled.search(value))
+            .ok()
+            .and_then(|res| serde_json::to_value(&*res).ok())
+        {
+            Some(val) if !val.is_null() => val,
+            _ => {
+                options.field_data_not_found(field, &expr);
+                serde_json::Value::Null
+            }
+        };
+    }
+    if options.snapshot_mode() {
+        sort_object_keys(&mut value);
+    }
+    value
+}
+
+/// Recursively sort keys of the json objects
+fn sort_object_keys(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.sort_keys();
+            map.values_mut().for_each(sort_object_keys);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(sort_object_keys),
+        _ => {}
+    }
+}
+
+/// Whether the value equals to the default value of its type
+///
+/// Used by the derived code for the `optional_default` fields.
+#[doc(hidden)]
+pub fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// Placeholder of the redacted field values
+pub const REDACTED_PLACEHOLDER: &str = "******";
+
+/// Default placeholder for the field data that failed to be serialized
+pub const SERIALIZATION_ERROR_PLACEHOLDER: &str = "<ERROR SERIALIZING DATA>";
+
+/// Emit the tracing event for the field data serialization error
+#[cfg(feature = "tracing")]
+fn trace_serialization_error(field: &str, error: &serde_json::Error) {
+    tracing::debug!(field, %error, "error serializing field data");
+}
+
+/// Apply the column width limit (if any) to the value
+fn limit_width<O: StructTableOptions>(
+    options: &O,
+    schema: &[FieldInfo],
+    field: &str,
+    value: String,
+) -> String {
+    match max_width(options, schema, field) {
+        Some(width) => truncate_value(value, width, ellipsis_position(options, schema, field)),
+        None => value,
+    }
+}
+
+/// Maximum width of the column values
+///
+/// Options take precedence over the field `max_length` attribute, which is ignored in the wide
+/// and pretty modes.
+fn max_width<O: StructTableOptions>(
+    options: &O,
+    schema: &[FieldInfo],
+    field: &str,
+) -> Option<usize> {
+    options
+        .column_max_width(field)
+        .or_else(|| match options.wide_mode() || options.pretty_mode() {
+            true => None,
+            false => schema
+                .iter()
+                .find(|info| info.title == field)
+                .and_then(|info| info.max_length),
+        })
+}
+
+/// Position of the ellipsis in the truncated values of the column
+///
+/// Options take precedence over the field `ellipsis` attribute.
+fn ellipsis_position<O: StructTableOptions>(
+    options: &O,
+    schema: &[FieldInfo],
+    field: &str,
+) -> EllipsisPosition {
+    options
+        .ellipsis_position(field)
+        .or_else(|| {
+            schema
+                .iter()
+                .find(|info| info.title == field)
+                .map(|info| info.ellipsis)
+        })
+        .unwrap_or_default()
+}
+
+/// Static information about the table column
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FieldInfo {
+    /// Column title
+    pub title: String,
+    /// Name of the structure field
+    pub name: String,
+    /// Whether the column is returned in the wide mode only
+    pub wide: bool,
+    /// Verbosity level required to return the column (`0` always, `1` in the wide mode)
+    pub verbosity: u8,
+    /// Whether the column is omitted from the list table when it is empty in every row
+    pub hide_if_empty: bool,
+    /// Column priority for the renderers deciding which columns to keep when the space is
+    /// limited. Lower value means more important column (`0` by default).
+    pub priority: u32,
+    /// Whether the column is (part of) the identity of the row
+    pub key: bool,
+    /// Example value of the column for the documentation and column pickers
+    pub example: Option<String>,
+    /// Text direction hint of the column values
+    pub direction: TextDirection,
+    /// Key of the localized column title
+    pub l10n_key: Option<String>,
+    /// Position of the ellipsis in the truncated values
+    pub ellipsis: EllipsisPosition,
+    /// Nested keys of the value are rendered as the separate `parent.child` rows of the single
+    /// structure table
+    pub expand: bool,
+    /// Horizontal alignment hint of the column values
+    pub align: Alignment,
+    /// Unit of the column values
+    pub unit: Option<String>,
+    /// Maximum length of the values outside of the wide and pretty modes
+    pub max_length: Option<usize>,
+    /// Style hint of the column values
+    pub style: CellStyle,
+    /// Groups the field belongs to
+    pub tags: Vec<String>,
+    /// Description of the column (doc comments of the field)
+    pub description: Option<String>,
+}
+
+/// Style hint of the column values for the renderers
+///
+/// Values are free-form (i.e. `bold` and `red`) and interpreted by the renderer, headers and
+/// values of the table stay plain strings.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CellStyle {
+    /// Text style (i.e. `bold`, `italic`, `dim`)
+    pub style: Option<String>,
+    /// Text color (i.e. `red` or `#ff0000`)
+    pub color: Option<String>,
+}
+
+/// Horizontal alignment of the column values
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alignment {
+    /// Aligned to the left
+    #[default]
+    Left,
+    /// Aligned to the right (i.e. numbers)
+    Right,
+    /// Centered
+    Center,
+}
+
+/// Table header annotated with its visibility under the given options
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HeaderInfo {
+    /// Column title
+    pub title: String,
+    /// Whether the column is returned with the options
+    pub visible: bool,
+    /// Horizontal alignment hint of the column values
+    pub align: Alignment,
+}
+
+/// Trait for building tables out of structures
+pub trait StructTable {
+    /// Return Vector of table headers (attribute titles to be returned) that are not instance
+    /// specific (i.e. struct)
+    fn class_headers<O: StructTableOptions>(_config: &O) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Return Vector of table headers (attribute titles to be returned) from the instance that are
+    /// instance specific (i.e. HashMap)
+    fn instance_headers<O: StructTableOptions>(&self, _config: &O) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Return vector of selected fields as `Option<String>`
+    fn data<O: StructTableOptions>(&self, config: &O) -> Vec<Option<String>>;
+
+    /// Return structure status property
+    fn status(&self) -> Option<String> {
+        None
+    }
+
+    /// Return values of all status properties with their titles
+    ///
+    /// Ordered with the primary status (returned by the [`status`](StructTable::status)) first.
+    fn statuses(&self) -> Vec<(String, String)> {
+        self.status()
+            .map(|status| Vec::from([(String::from("status"), status)]))
+            .unwrap_or_default()
+    }
+
+    /// Return hyperlink targets of the cells by the column titles (fields with the `link`)
+    fn links(&self) -> BTreeMap<String, String> {
+        BTreeMap::new()
+    }
+
+    /// Return selected fields together with the structure status
+    ///
+    /// Equivalent to calling [`data`](StructTable::data) and [`status`](StructTable::status), but
+    /// allows implementations to serialize a field used for both only once.
+    fn data_with_status<O: StructTableOptions>(
+        &self,
+        config: &O,
+    ) -> (Vec<Option<String>>, Option<String>) {
+        (self.data(config), self.status())
+    }
+
+    /// Return information about all columns of the structure independently of the options
+    fn schema() -> Vec<FieldInfo> {
+        Vec::new()
+    }
+
+    /// Return descriptions (doc comments of the fields) of the columns by their titles
+    ///
+    /// Columns without the description are omitted.
+    fn field_descriptions() -> Vec<(String, String)> {
+        Self::schema()
+            .into_iter()
+            .filter_map(|info| {
+                info.description
+                    .map(|description| (info.title, description))
+            })
+            .collect()
+    }
+
+    /// Return all class headers annotated with their visibility instead of filtering them
+    ///
+    /// Useful to indicate number of hidden columns or to build column pickers.
+    fn class_headers_all<O: StructTableOptions>(options: &O) -> Vec<HeaderInfo> {
+        Self::schema()
+            .into_iter()
+            .map(|field| HeaderInfo {
+                visible: column_visible(options, &field),
+                title: field.title,
+                align: field.align,
+            })
+            .collect()
+    }
+
+    /// Return alignment of the columns of the built table
+    ///
+    /// `headers` are the (possibly renamed or localized) titles returned by the table builders.
+    /// Columns not described by the [`StructTable::schema`] are aligned to the left.
+    fn column_alignments<O: StructTableOptions>(headers: &[String], options: &O) -> Vec<Alignment> {
+        let schema = Self::schema();
+        column_infos(&schema, headers, options)
+            .into_iter()
+            .map(|info| info.map(|info| info.align).unwrap_or_default())
+            .collect()
+    }
+
+    /// Return style hints of the columns of the built table
+    ///
+    /// `headers` are the (possibly renamed or localized) titles returned by the table builders.
+    /// Columns not described by the [`StructTable::schema`] have no style.
+    fn column_styles<O: StructTableOptions>(headers: &[String], options: &O) -> Vec<CellStyle> {
+        let schema = Self::schema();
+        column_infos(&schema, headers, options)
+            .into_iter()
+            .map(|info| info.map(|info| info.style.clone()).unwrap_or_default())
+            .collect()
+    }
+
+    /// Return titles of the columns uniquely identifying the row
+    fn key_columns() -> Vec<String> {
+        Self::schema()
+            .into_iter()
+            .filter(|field| field.key)
+            .map(|field| field.title)
+            .collect()
+    }
+
+    /// Return titles of the columns returned only in the `wide` mode
+    fn wide_columns() -> Vec<String> {
+        Self::schema()
+            .into_iter()
+            .filter(|field| field.wide)
+            .map(|field| field.title)
+            .collect()
+    }
+
+    /// Return titles of the columns not returned with the given options
+    ///
+    /// Allows to hint users which additional columns can be requested.
+    fn hidden_columns<O: StructTableOptions>(options: &O) -> Vec<String> {
+        Self::schema()
+            .into_iter()
+            .filter(|field| !column_visible(options, field))
+            .map(|field| field.title)
+            .collect()
+    }
+}
+
+impl<T: StructTable> StructTable for &T {
+    fn class_headers<O: StructTableOptions>(config: &O) -> Option<Vec<String>> {
+        T::class_headers(config)
+    }
+
+    fn instance_headers<O: StructTableOptions>(&self, config: &O) -> Option<Vec<String>> {
+        (*self).instance_headers(config)
+    }
+
+    fn data<O: StructTableOptions>(&self, config: &O) -> Vec<Option<String>> {
+        (*self).data(config)
+    }
+
+    fn status(&self) -> Option<String> {
+        (*self).status()
+    }
+
+    fn statuses(&self) -> Vec<(String, String)> {
+        (*self).statuses()
+    }
+
+    fn links(&self) -> BTreeMap<String, String> {
+        (*self).links()
+    }
+
+    fn data_with_status<O: StructTableOptions>(
+        &self,
+        config: &O,
+    ) -> (Vec<Option<String>>, Option<String>) {
+        (*self).data_with_status(config)
+    }
+
+    fn schema() -> Vec<FieldInfo> {
+        T::schema()
+    }
+}
+
+/// Build a table for a single structure
+///
+/// Returns a vector with first row being column headers ["Attribute", "Value"]. All other rows
+/// represent transposed table with first value in the vector being an attribute name and second
+/// value being the value itself. The optional attribute, which is `None` is not being returned.
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn build_table<T, O>(data: &T, options: &O) -> (Vec<String>, Vec<Vec<String>>)
+where
+    T: StructTable,
+    O: StructTableOptions,
+{
+    let headers = Vec::from([
+        options
+            .localize(l10n::ATTRIBUTE)
+            .unwrap_or_else(|| "Attribute".into()),
+        options
+            .localize(l10n::VALUE)
+            .unwrap_or_else(|| "Value".into()),
+    ]);
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let col_headers = T::class_headers(options).or_else(|| data.instance_headers(options));
+    if let Some(hdr) = col_headers {
+        let schema = T::schema();
+        let links = match options.hyperlinks() {
+            true => data.links(),
+            false => BTreeMap::new(),
+        };
+        for (a, v) in hdr.iter().zip(data.data(options).iter()) {
+            if let Some(data) = v {
+                let title = l10n::output_title(options, &schema, a);
+                if options.redact_field(a) {
+                    rows.push(Vec::from([title, l10n::redacted_placeholder(options)]));
+                    continue;
+                }
+                // Only values of the `expand` fields are parsed back
+                if schema.iter().any(|info| info.title == *a && info.expand) {
+                    if let Ok(value @ serde_json::Value::Object(_)) =
+                        serde_json::from_str::<serde_json::Value>(data)
+                    {
+                        let mut expanded: Vec<(String, String)> = Vec::new();
+                        expand_value(title, value, &mut expanded);
+                        rows.extend(expanded.into_iter().map(|(attr, data)| {
+                            Vec::from([attr, limit_width(options, &schema, a, data)])
+                        }));
+                        continue;
+                    }
+                }
+                let value = limit_width(options, &schema, a, data.to_string());
+                rows.push(Vec::from([
+                    title,
+                    match links.get(a) {
+                        Some(url) => hyperlink(url, &value),
+                        None => value,
+                    },
+                ]));
+            }
+        }
+    }
+    debug_assert!(
+        table::check_rows(&headers, &rows).is_empty(),
+        "table rows are not aligned with the headers"
+    );
+    #[cfg(feature = "tracing")]
+    tracing::debug!(rows = rows.len(), "table built");
+    (headers, rows)
+}
+
+/// Whether the column described by the `field` is returned with the `options`
+fn column_visible<O: StructTableOptions>(options: &O, field: &FieldInfo) -> bool {
+    let tags: Vec<&str> = field.tags.iter().map(String::as_str).collect();
+    options.should_return_tagged_column(&field.title, &field.name, field.verbosity, &tags)
+}
+
+/// Find the schema entries of the output `headers`
+fn column_infos<'a, O: StructTableOptions>(
+    schema: &'a [FieldInfo],
+    headers: &[String],
+    options: &O,
+) -> Vec<Option<&'a FieldInfo>> {
+    headers
+        .iter()
+        .map(|hdr| {
+            schema
+                .iter()
+                .find(|info| l10n::output_title(options, schema, &info.title) == *hdr)
+        })
+        .collect()
+}
+
+/// Flatten the nested objects of the `value` into the `prefix.key` attributes
+///
+/// Keys without value (`null`) are skipped, other values are rendered as in the serialized
+/// fields.
+fn expand_value(prefix: String, value: serde_json::Value, rows: &mut Vec<(String, String)>) {
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => {
+            for (key, val) in map {
+                expand_value(format!("{prefix}.{key}"), val, rows);
+            }
+        }
+        serde_json::Value::Null => {}
+        serde_json::Value::String(val) => rows.push((prefix, val)),
+        other => rows.push((prefix, other.to_string())),
+    }
+}
+
+/// Collect headers and raw row data of the list entries
+///
+/// When the structure does not provide class headers instance headers of every entry are merged
+/// (in the order of their first appearance) and entry data is aligned to them. Data of every entry
+/// is passed through the `transform`.
+fn collect_list_data<I, T, O, F>(
+    data: I,
+    options: &O,
+    mut transform: F,
+) -> (Vec<String>, Vec<Vec<Option<String>>>)
+where
+    I: Iterator<Item = T>,
+    T: StructTable,
+    O: StructTableOptions,
+    F: FnMut(&T, Vec<Option<String>>) -> Vec<Option<String>>,
+{
+    if let Some(headers) = T::class_headers(options) {
+        let rows: Vec<Vec<Option<String>>> =
+            Vec::from_iter(data.map(|item| transform(&item, item.data(options))));
+        (headers, rows)
+    } else {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("structure does not provide class headers");
+        let mut headers: Vec<String> = Vec::new();
+        let mut entries: Vec<BTreeMap<String, Option<String>>> = Vec::new();
+        for item in data {
+            let item_headers = item.instance_headers(options).unwrap_or_default();
+            for hdr in item_headers.iter() {
+                if !headers.contains(hdr) {
+                    headers.push(hdr.clone());
+                }
+            }
+            let row = transform(&item, item.data(options));
+            entries.push(item_headers.into_iter().zip(row).collect());
+        }
+        let rows: Vec<Vec<Option<String>>> = entries
+            .into_iter()
+            .map(|mut entry| {
+                headers
+                    .iter()
+                    .map(|hdr| entry.remove(hdr).flatten())
+                    .collect()
+            })
+            .collect();
+        (headers, rows)
+    }
+}
+
+/// Remove columns selected by the `filter` for which all rows contain no data
+fn drop_empty_columns<F>(headers: &mut Vec<String>, rows: &mut [Vec<Option<String>>], filter: F)
+where
+    F: Fn(&String) -> bool,
+{
+    let keep: Vec<bool> = headers
+        .iter()
+        .enumerate()
+        .map(|(idx, hdr)| {
+            !filter(hdr)
+                || rows.iter().any(|row| {
+                    row.get(idx)
+                        .is_some_and(|cell| cell.as_ref().is_some_and(|val| !val.trim().is_empty()))
+                })
+        })
+        .collect();
+    if keep.iter().all(|x| *x) {
+        return;
+    }
+    let mut idx = 0;
+    headers.retain(|_| {
+        idx += 1;
+        keep[idx - 1]
+    });
+    for row in rows.iter_mut() {
+        let mut idx = 0;
+        row.retain(|_| {
+            idx += 1;
+            keep.get(idx - 1).copied().unwrap_or(true)
+        });
+    }
+}
+
+/// Build a table for list of entries
+///
+/// Returns vector of vector of strings with first row being table headers and all other rows are
+/// the values themselves. Missing values are represented by the `text` of the
+/// [`StructTableOptions::null_policy`].
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+pub fn build_list_table<I, T, O>(data: I, options: &O) -> (Vec<String>, Vec<Vec<String>>)
+where
+    I: Iterator<Item = T>,
+    T: StructTable,
+    O: StructTableOptions,
+{
+    build_list_table_with_null(
+        data,
+        options,
+        &options.null_policy().text,
+        None,
+        options.max_rows(),
+        |_: &T, row| row,
+    )
+}
+
+/// Build a table for list of entries post-processing the data of every entry
+///
+/// Same as [`build_list_table`], but the `transform` is invoked with every selected entry and its
+/// row data (aligned to the entry headers) before further processing. It allows to inject computed
+/// cells, redact or annotate values without iterating the source data again. The number of cells
+/// must stay the same.
+pub fn build_list_table_with<I, T, O, F>(
+    data: I,
+    options: &O,
+    transform: F,
+) -> (Vec<String>, Vec<Vec<String>>)
+where
+    I: Iterator<Item = T>,
+    T: StructTable,
+    O: StructTableOptions,
+    F: FnMut(&T, Vec<Option<String>>) -> Vec<Option<String>>,
+{
+    build_list_table_with_null(
+        data,
+        options,
+        &options.null_policy().text,
+        None,
+        options.max_rows(),
+        transform,
+    )
+}
+
+/// Build a table for list of entries together with the non-fatal warnings
+///
+/// Same as [`build_list_table`], but additionally returns requested fields not matching any
+/// column, field data errors replaced with placeholders and truncated columns.
+pub fn build_list_table_with_warnings<I, T, O>(
+    data: I,
+    options: &O,
+) -> (Vec<String>, Vec<Vec<String>>, Vec<Warning>)
+where
+    I: Iterator<Item = T>,
+    T: StructTable,
+    O: StructTableOptions,
+{
+    let collector = ErrorCollector::new(options);
+    let mut truncated: BTreeMap<String, usize> = BTreeMap::new();
+    let (headers, rows) = build_list_table_with_null(
+        data,
+        &collector,
+        &options.null_policy().text,
+        Some(&mut truncated),
+        options.max_rows(),
+        |_: &T, row| row,
+    );
+    let schema = T::schema();
+    let mut warnings: Vec<Warning> = options
+        .requested_fields()
+        .into_iter()
+        .filter(|field| {
+            !headers.iter().any(|hdr| titles_match(hdr, field))
+                && !schema.iter().any(|info| titles_match(&info.title, field))
+        })
+        .map(|field| Warning::UnknownField { field })
+        .collect();
+    warnings.extend(collector.into_errors().into_iter().map(Warning::Data));
+    warnings.extend(
+        truncated
+            .into_iter()
+            .map(|(field, cells)| Warning::Truncated { field, cells }),
+    );
+    (headers, rows, warnings)
+}
+
+/// Build a list table and format it as CSV
+///
+/// Missing values are represented by the `csv` of the [`StructTableOptions::null_policy`]. The
+/// [`StructTableOptions::max_rows`] is ignored to not add the trailer row into the data records.
+pub fn build_csv_table<I, T, O>(data: I, options: &O, csv_options: &CsvOptions) -> String
+where
+    I: Iterator<Item = T>,
+    T: StructTable,
+    O: StructTableOptions,
+{
+    let (headers, rows) = build_list_table_with_null(
+        data,
+        options,
+        &options.null_policy().csv,
+        None,
+        None,
+        |_: &T, row| row,
+    );
+    format_csv(&headers, &rows, csv_options)
+}
+
+/// Build a table for list of entries representing missing values with `null`
+///
+/// Number of truncated cells per column is counted in `truncated` when given. Rows above the
+/// `max_rows` are replaced with the trailer row.
+pub(crate) fn build_list_table_with_null<I, T, O, F>(
+    data: I,
+    options: &O,
+    null: &str,
+    mut truncated: Option<&mut BTreeMap<String, usize>>,
+    max_rows: Option<usize>,
+    transform: F,
+) -> (Vec<String>, Vec<Vec<String>>)
+where
+    I: Iterator<Item = T>,
+    T: StructTable,
+    O: StructTableOptions,
+    F: FnMut(&T, Vec<Option<String>>) -> Vec<Option<String>>,
+{
+    // Hyperlink targets of the rows (wrapped around the values after the truncation)
+    let hyperlinks = options.hyperlinks();
+    let mut links: Vec<BTreeMap<String, String>> = Vec::new();
+    let mut transform = transform;
+    let transform = |item: &T, row: Vec<Option<String>>| {
+        if hyperlinks {
+            links.push(item.links());
+        }
+        transform(item, row)
+    };
+    let mut data = data.take(options.head_rows().unwrap_or(usize::MAX));
+    let max_rows = max_rows.unwrap_or(usize::MAX);
+    // Entries above the `max_rows` are only counted
+    let ((mut headers, mut data), omitted_rows) = match options.tail_rows() {
+        Some(tail) => {
+            // Buffer only the last `tail` entries
+            let mut selected: VecDeque<T> = VecDeque::with_capacity(tail);
+            for item in data {
+                if selected.len() == tail {
+                    selected.pop_front();
+                }
+                if tail > 0 {
+                    selected.push_back(item);
+                }
+            }
+            let omitted_rows = selected.len().saturating_sub(max_rows);
+            (
+                collect_list_data(selected.into_iter().take(max_rows), options, transform),
+                omitted_rows,
+            )
+        }
+        None => {
+            let table = collect_list_data(data.by_ref().take(max_rows), options, transform);
+            (table, data.count())
+        }
+    };
+    let redacted = l10n::redacted_placeholder(options);
+    for (idx, _) in headers
+        .iter()
+        .enumerate()
+        .filter(|(_, hdr)| options.redact_field(hdr))
+    {
+        for cell in data.iter_mut().filter_map(|row| row.get_mut(idx)) {
+            if cell.is_some() {
+                *cell = Some(redacted.clone());
+            }
+        }
+    }
+    let schema = T::schema();
+    let hidden: Vec<&String> = schema
+        .iter()
+        .filter(|field| field.hide_if_empty)
+        .map(|field| &field.title)
+        .collect();
+    if options.hide_empty_columns() {
+        drop_empty_columns(&mut headers, &mut data, |_| true);
+    } else if !hidden.is_empty() {
+        drop_empty_columns(&mut headers, &mut data, |hdr| hidden.contains(&hdr));
+    }
+    let widths: Vec<Option<(usize, EllipsisPosition)>> = headers
+        .iter()
+        .map(|hdr| {
+            max_width(options, &schema, hdr)
+                .map(|width| (width, ellipsis_position(options, &schema, hdr)))
+        })
+        .collect();
+    let mut rows: Vec<Vec<String>> =
+        Vec::from_iter(data.into_iter().enumerate().map(|(idx, row)| {
+            row.into_iter()
+                .zip(widths.iter().zip(headers.iter()))
+                .map(|(el, (width, hdr))| {
+                    let link = match el {
+                        Some(_) if !options.redact_field(hdr) => {
+                            links.get(idx).and_then(|links| links.get(hdr))
+                        }
+                        _ => None,
+                    };
+                    let val = el.unwrap_or_else(|| null.to_string());
+                    let val = match width {
+                        Some((width, position)) => {
+                            if let Some(counts) = truncated.as_deref_mut() {
+                                if val.chars().count() > *width {
+                                    *counts.entry(hdr.clone()).or_default() += 1;
+                                }
+                            }
+                            truncate_value(val, *width, *position)
+                        }
+                        None => val,
+                    };
+                    match link {
+                        Some(url) => hyperlink(url, &val),
+                        None => val,
+                    }
+                })
+                .collect::<Vec<String>>()
+        }));
+    for hdr in headers.iter_mut() {
+        *hdr = l10n::output_title(options, &schema, hdr);
+    }
+    if omitted_rows > 0 && !headers.is_empty() {
+        let mut trailer = vec![String::new(); headers.len()];
+        trailer[0] = l10n::more_rows_trailer(options, omitted_rows);
+        rows.push(trailer);
+    }
+    debug_assert!(
+        table::check_rows(&headers, &rows).is_empty(),
+        "list table rows are not aligned with the headers"
+    );
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        columns = headers.len(),
+        rows = rows.len(),
+        "list table built"
+    );
+    (headers, rows)
+}
+
+/// Build a table for list of entry results
+///
+/// Successful entries are processed the same way as in [`build_list_table`], errors are returned
+/// separately as the last tuple element in the order of their appearance.
+pub fn build_list_table_results<I, T, E, O>(
+    data: I,
+    options: &O,
+) -> (Vec<String>, Vec<Vec<String>>, Vec<E>)
+where
+    I: Iterator<Item = Result<T, E>>,
+    T: StructTable,
+    O: StructTableOptions,
+{
+    let mut errors: Vec<E> = Vec::new();
+    let (headers, rows) = build_list_table(
+        data.filter_map(|item| item.map_err(|err| errors.push(err)).ok()),
+        options,
+    );
+    (headers, rows, errors)
+}
+
+/// Build a table for a single structure failing on data errors in the strict mode
+///
+/// Same as [`build_table`], but when [`StructTableOptions::strict_mode`] is enabled the first
+/// data error (field serialization error, unresolved json pointer) is returned instead of the
+/// table with placeholders.
+pub fn try_build_table<T, O>(
+    data: &T,
+    options: &O,
+) -> Result<(Vec<String>, Vec<Vec<String>>), StructTableError>
+where
+    T: StructTable,
+    O: StructTableOptions,
+{
+    if !options.strict_mode() {
+        return Ok(build_table(data, options));
+    }
+    let collector = ErrorCollector::new(options);
+    let table = build_table(data, &collector);
+    collector.into_result(table)
+}
+
+/// Build a table for list of entries failing on data errors in the strict mode
+///
+/// Same as [`build_list_table`], but when [`StructTableOptions::strict_mode`] is enabled the
+/// first data error (field serialization error, unresolved json pointer) is returned instead of
+/// the table with placeholders.
+pub fn try_build_list_table<I, T, O>(
+    data: I,
+    options: &O,
+) -> Result<(Vec<String>, Vec<Vec<String>>), StructTableError>
+where
+    I: Iterator<Item = T>,
+    T: StructTable,
+    O: StructTableOptions,
+{
+    if !options.strict_mode() {
+        return Ok(build_list_table(data, options));
+    }
+    let collector = ErrorCollector::new(options);
+    let table = build_list_table(data, &collector);
+    collector.into_result(table)
+}
+
+/// Return statuses of the entries by their identity
+///
+/// The key is built from the values of the [`StructTable::key_columns`] (joined with `/` when
+/// there are multiple key columns). Entries without status are skipped. The map is empty when the
+/// structure has no key columns.
+pub fn status_map<I, T>(data: I) -> BTreeMap<String, String>
+where
+    I: Iterator<Item = T>,
+    T: StructTable,
+{
+    let keys = T::key_columns();
+    let mut res: BTreeMap<String, String> = BTreeMap::new();
+    if keys.is_empty() {
+        return res;
+    }
+    // Key columns must be present independently of their verbosity level
+    let options = OutputConfig {
+        verbosity: u8::MAX,
+        ..Default::default()
+    };
+    let class_headers = T::class_headers(&options);
+    for item in data {
+        // Status and the row share the serialized values
+        let (row, Some(status)) = item.data_with_status(&options) else {
+            continue;
+        };
+        let headers = class_headers
+            .clone()
+            .or_else(|| item.instance_headers(&options))
+            .unwrap_or_default();
+        let key = keys
+            .iter()
+            .map(|key| {
+                headers
+                    .iter()
+                    .position(|hdr| hdr == key)
+                    .and_then(|idx| row.get(idx).cloned().flatten())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<String>>()
+            .join("/");
+        res.insert(key, status);
+    }
+    res
+}
+
+/// Build a summary table counting entries by their status
+///
+/// Returns vector of vector of strings with first row being table headers ["Status", "Count"]
+/// (localized with the [`l10n::STATUS`] and [`l10n::COUNT`] keys) and all other rows representing
+/// the number of entries with the corresponding status. Entries without status are counted under
+/// the [`l10n::NO_STATUS`] label falling back to the `text` of the
+/// [`StructTableOptions::null_policy`].
+pub fn build_summary_table<I, T, O>(data: I, options: &O) -> (Vec<String>, Vec<Vec<String>>)
+where
+    I: Iterator<Item = T>,
+    T: StructTable,
+    O: StructTableOptions,
+{
+    let headers = Vec::from([
+        options
+            .localize(l10n::STATUS)
+            .unwrap_or_else(|| "Status".into()),
+        options
+            .localize(l10n::COUNT)
+            .unwrap_or_else(|| "Count".into()),
+    ]);
+    let no_status = options
+        .localize(l10n::NO_STATUS)
+        .unwrap_or_else(|| options.null_policy().text);
+    let mut counts: BTreeMap<Option<String>, usize> = BTreeMap::new();
+    for item in data {
+        *counts.entry(item.status()).or_default() += 1;
+    }
+    let rows: Vec<Vec<String>> = counts
+        .into_iter()
+        .map(|(status, count)| {
+            Vec::from([
+                status.unwrap_or_else(|| no_status.clone()),
+                count.to_string(),
+            ])
+        })
+        .collect();
+    (headers, rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[derive(Default, Deserialize, Serialize, StructTable)]
+    struct User {
+        #[structable(title = "ID")]
+        id: u64,
+        first_name: String,
+        last_name: String,
+        #[structable(title = "Long", wide)]
+        extra: String,
+        #[structable(optional, serialize, wide)]
+        complex_data: Option<Value>,
+        #[structable(optional)]
+        dummy: Option<String>,
+    }
+
+    #[derive(Deserialize, Serialize, StructTable)]
+    struct StatusStruct {
+        #[structable(status)]
+        status: String,
+    }
+
+    #[derive(Clone, Deserialize, Serialize)]
+    enum Status {
+        Dummy,
+    }
+
+    #[derive(Deserialize, Serialize, StructTable)]
+    struct SerializeStatusStruct {
+        #[structable(serialize, status)]
+        status: Status,
+    }
+
+    #[derive(Deserialize, Serialize, StructTable)]
+    struct SerializeOptionStatusStruct {
+        #[structable(optional, serialize, status)]
+        status: Option<Status>,
+    }
+
+    #[derive(Deserialize, Serialize, StructTable)]
+    struct OptionStatusStruct {
+        #[structable(status, optional)]
+        status: Option<String>,
+    }
+
+    #[test]
+    fn test_single() {
+        let config = OutputConfig::default();
+        let user = User {
+            id: 1,
+            first_name: "Scooby".into(),
+            last_name: "Doo".into(),
+            extra: "XYZ".into(),
+            complex_data: Some(json!({"a": "b", "c": "d"})),
+            dummy: None,
+        };
+        assert_eq!(
+            build_table(&user, &config),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![
+                    vec!["ID".into(), "1".into()],
+                    vec!["first_name".into(), "Scooby".into()],
+                    vec!["last_name".into(), "Doo".into()],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_single_wide() {
+        let config = OutputConfig {
+            wide: true,
+            ..Default::default()
+        };
+        let user = User {
+            id: 1,
+            first_name: "Scooby".into(),
+            last_name: "Doo".into(),
+            extra: "XYZ".into(),
+            complex_data: Some(json!({"a": "b", "c": "d"})),
+            dummy: None,
+        };
+        assert_eq!(
+            build_table(&user, &config),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![
+                    vec!["ID".into(), "1".into()],
+                    vec!["first_name".into(), "Scooby".into()],
+                    vec!["last_name".into(), "Doo".into()],
+                    vec!["Long".into(), "XYZ".into()],
+                    vec![
+                        "complex_data".into(),
+                        "{\"a\":\"b\",\"c\":\"d\"}".to_string()
+                    ],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_single_wide_column() {
+        let config = OutputConfig {
+            fields: BTreeSet::from(["Long".into()]),
+            ..Default::default()
+        };
+        let user = User {
+            id: 1,
+            first_name: "Scooby".into(),
+            last_name: "Doo".into(),
+            extra: "XYZ".into(),
+            complex_data: Some(json!({"a": "b", "c": "d"})),
+            dummy: None,
+        };
+        assert_eq!(
+            build_table(&user, &config),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![vec!["Long".into(), "XYZ".into()],]
+            )
+        );
+    }
+
+    #[test]
+    fn test_single_wide_column_wide_mode() {
+        let config = OutputConfig {
+            fields: BTreeSet::from(["Long".into()]),
+            wide: true,
+            ..Default::default()
+        };
+        let user = User {
+            id: 1,
+            first_name: "Scooby".into(),
+            last_name: "Doo".into(),
+            extra: "XYZ".into(),
+            complex_data: Some(json!({"a": "b", "c": "d"})),
+            dummy: None,
+        };
+        assert_eq!(
+            build_table(&user, &config),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![vec!["Long".into(), "XYZ".into()],]
+            )
+        );
+    }
+
+    #[test]
+    fn test_single_wide_pretty() {
+        let config = OutputConfig {
+            wide: true,
+            pretty: true,
+            ..Default::default()
+        };
+        let user = User {
+            id: 1,
+            first_name: "Scooby".into(),
+            last_name: "Doo".into(),
+            extra: "XYZ".into(),
+            complex_data: Some(json!({"a": "b", "c": "d"})),
+            dummy: None,
+        };
+        assert_eq!(
+            build_table(&user, &config),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![
+                    vec!["ID".into(), "1".into()],
+                    vec!["first_name".into(), "Scooby".into()],
+                    vec!["last_name".into(), "Doo".into()],
+                    vec!["Long".into(), "XYZ".into()],
+                    vec![
+                        "complex_data".into(),
+                        "{\n  \"a\": \"b\",\n  \"c\": \"d\"\n}".to_string()
+                    ],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_single_status() {
+        assert_eq!(
+            StatusStruct {
+                status: "foo".into(),
+            }
+            .status(),
+            Some("foo".into())
+        );
+    }
+
+    #[test]
+    fn test_single_no_status() {
+        assert_eq!(User::default().status(), None);
+    }
+
+    #[test]
+    fn test_single_option_status() {
+        assert_eq!(
+            OptionStatusStruct {
+                status: Some("foo".into()),
+            }
+            .status(),
+            Some("foo".into())
+        );
+    }
+
+    #[test]
+    fn test_complex_status() {
+        assert_eq!(
+            SerializeStatusStruct {
+                status: Status::Dummy,
+            }
+            .status(),
+            Some("Dummy".into())
+        );
+
+        assert_eq!(
+            SerializeOptionStatusStruct {
+                status: Some(Status::Dummy),
+            }
+            .status(),
+            Some("Dummy".into())
+        );
+
+        let (_, rows) = build_table(
+            &SerializeOptionStatusStruct {
+                status: Some(Status::Dummy),
+            },
+            &OutputConfig::default(),
+        );
+        assert_eq!(vec![vec!["status".to_string(), "Dummy".to_string()]], rows);
+
+        let (_, rows) = build_list_table(
+            [SerializeOptionStatusStruct {
+                status: Some(Status::Dummy),
+            }]
+            .iter(),
+            &OutputConfig::default(),
+        );
+        assert_eq!(vec![vec!["Dummy".to_string()]], rows);
+    }
+
+    #[test]
+    fn test_ref_impl() {
+        let user = User {
+            id: 1,
+            ..Default::default()
+        };
+        let config = OutputConfig::default();
+        assert_eq!(
+            <&User>::class_headers(&config),
+            User::class_headers(&config)
+        );
+        let user_ref = &user;
+        assert_eq!(StructTable::data(&user_ref, &config), user.data(&config));
+        assert_eq!(StructTable::status(&user_ref), user.status());
+    }
+
+    #[test]
+    fn test_summary() {
+        let data = [
+            OptionStatusStruct {
+                status: Some("b".into()),
+            },
+            OptionStatusStruct {
+                status: Some("a".into()),
+            },
+            OptionStatusStruct { status: None },
+            OptionStatusStruct {
+                status: Some("b".into()),
+            },
+        ];
+        assert_eq!(
+            build_summary_table(data.iter(), &OutputConfig::default()),
+            (
+                vec!["Status".into(), "Count".into()],
+                vec![
+                    vec![" ".into(), "1".into()],
+                    vec!["a".into(), "1".into()],
+                    vec!["b".into(), "2".into()],
+                ]
+            )
+        );
+        let config = OutputConfig {
+            null_policy: NullPolicy {
+                text: "-".into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            build_summary_table(data.iter(), &config).1[0],
+            vec!["-".to_string(), "1".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_status() {
+        #[derive(Deserialize, Serialize, StructTable)]
+        struct StatusStruct {
+            #[structable(title = "ID")]
+            id: u64,
+            #[structable(status)]
+            status: String,
+        }
+
+        assert_eq!(
+            StatusStruct {
+                id: 1,
+                status: "foo".into(),
+            }
+            .status(),
+            Some("foo".into())
+        );
+    }
+
+    #[test]
+    fn test_list() {
+        let config = OutputConfig::default();
+        let users = [
+            User {
+                id: 1,
+                first_name: "Scooby".into(),
+                last_name: "Doo".into(),
+                extra: "Foo".into(),
+                complex_data: Some(json!({"a": "b", "c": "d"})),
+                dummy: None,
+            },
+            User {
+                id: 2,
+                first_name: "John".into(),
+                last_name: "Cena".into(),
+                extra: "Bar".into(),
+                complex_data: None,
+                dummy: None,
+            },
+        ];
+
+        assert_eq!(
+            build_list_table(users.iter(), &config),
+            (
+                vec![
+                    "ID".into(),
+                    "first_name".into(),
+                    "last_name".into(),
+                    "dummy".into()
+                ],
+                vec![
+                    vec!["1".into(), "Scooby".into(), "Doo".into(), " ".into()],
+                    vec!["2".into(), "John".into(), "Cena".into(), " ".into()],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_list_wide_column() {
+        let config = OutputConfig {
+            fields: BTreeSet::from(["Long".into()]),
+            ..Default::default()
+        };
+        let users = [
+            User {
+                id: 1,
+                first_name: "Scooby".into(),
+                last_name: "Doo".into(),
+                extra: "Foo".into(),
+                complex_data: Some(json!({"a": "b", "c": "d"})),
+                dummy: None,
+            },
+            User {
+                id: 2,
+                first_name: "John".into(),
+                last_name: "Cena".into(),
+                extra: "Bar".into(),
+                complex_data: None,
+                dummy: Some("foo".into()),
+            },
+        ];
+
+        assert_eq!(
+            build_list_table(users.iter(), &config),
+            (
+                vec!["Long".into(),],
+                vec![vec!["Foo".into(),], vec!["Bar".into(),],]
+            )
+        );
+    }
+
+    #[test]
+    fn test_list_wide_column_wide_mode() {
+        let config = OutputConfig {
+            fields: BTreeSet::from(["Long".into()]),
+            wide: true,
+            pretty: false,
+            ..Default::default()
+        };
+        let users = [
+            User {
+                id: 1,
+                first_name: "Scooby".into(),
+                last_name: "Doo".into(),
+                extra: "Foo".into(),
+                complex_data: Some(json!({"a": "b", "c": "d"})),
+                dummy: None,
+            },
+            User {
+                id: 2,
+                first_name: "John".into(),
+                last_name: "Cena".into(),
+                extra: "Bar".into(),
+                complex_data: None,
+                dummy: Some("foo".into()),
+            },
+        ];
+
+        assert_eq!(
+            build_list_table(users.iter(), &config),
+            (
+                vec!["Long".into(),],
+                vec![vec!["Foo".into(),], vec!["Bar".into(),],]
+            )
+        );
+    }
+
+    #[test]
+    fn test_list_wide() {
+        let config = OutputConfig {
+            fields: BTreeSet::new(),
+            wide: true,
+            pretty: false,
+            ..Default::default()
+        };
+        let users = [
+            User {
+                id: 1,
+                first_name: "Scooby".into(),
+                last_name: "Doo".into(),
+                extra: "Foo".into(),
+                complex_data: Some(json!({"a": "b", "c": "d"})),
+                dummy: None,
+            },
+            User {
+                id: 2,
+                first_name: "John".into(),
+                last_name: "Cena".into(),
+                extra: "Bar".into(),
+                complex_data: None,
+                dummy: Some("foo".into()),
+            },
+        ];
+
+        assert_eq!(
+            build_list_table(users.iter(), &config),
+            (
+                vec![
+                    "ID".into(),
+                    "first_name".into(),
+                    "last_name".into(),
+                    "Long".into(),
+                    "complex_data".into(),
+                    "dummy".into()
+                ],
+                vec![
+                    vec![
+                        "1".into(),
+                        "Scooby".into(),
+                        "Doo".into(),
+                        "Foo".into(),
+                        "{\"a\":\"b\",\"c\":\"d\"}".to_string(),
+                        " ".to_string()
+                    ],
+                    vec![
+                        "2".into(),
+                        "John".into(),
+                        "Cena".into(),
+                        "Bar".into(),
+                        " ".to_string(),
+                        "foo".into()
+                    ],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_column_widths() {
+        let config = OutputConfig {
+            column_widths: HashMap::from([("first_name".into(), 4), ("id".into(), 0)]),
+            ..Default::default()
+        };
+        let user = User {
+            id: 1,
+            first_name: "Scooby".into(),
+            last_name: "Doo".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_table(&user, &config),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![
+                    vec!["ID".into(), "".into()],
+                    vec!["first_name".into(), "Sco…".into()],
+                    vec!["last_name".into(), "Doo".into()],
+                ]
+            )
+        );
+        assert_eq!(
+            build_list_table([user].iter(), &config),
+            (
+                vec![
+                    "ID".into(),
+                    "first_name".into(),
+                    "last_name".into(),
+                    "dummy".into()
+                ],
+                vec![vec!["".into(), "Sco…".into(), "Doo".into(), " ".into()]]
+            )
+        );
+    }
+
+    #[test]
+    fn test_deser() {
+        #[derive(Deserialize, Serialize, StructTable)]
+        struct Foo {
+            #[structable(title = "ID")]
+            id: u64,
+            #[structable(optional)]
+            foo: Option<String>,
+            #[structable(optional)]
+            bar: Option<bool>,
+        }
+
+        let foo: Foo = serde_json::from_value(json!({"id": 1})).expect("Foo object");
+
+        assert_eq!(
+            build_table(&foo, &OutputConfig::default()),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![vec!["ID".into(), "1".into()],]
+            )
+        );
+    }
+
+    #[test]
+    fn test_output_config() {
+        let config = OutputConfig {
+            fields: BTreeSet::from(["Foo".into(), "bAr".into(), "BAZ".into(), "a:b-c".into()]),
+            ..Default::default()
+        };
+
+        assert!(config.should_return_field("Foo", false));
+        assert!(config.should_return_field("FOO", false));
+        assert!(config.should_return_field("bar", false));
+        assert!(config.should_return_field("baz", false));
+        assert!(config.should_return_field("a:b-c", false));
+    }
+
+    #[cfg(feature = "unicase")]
+    #[test]
+    fn test_output_config_case_folding() {
+        let config = OutputConfig {
+            fields: BTreeSet::from(["STRASSE".into(), "Größe".into()]),
+            ..Default::default()
+        };
+
+        assert!(config.should_return_field("straße", false));
+        assert!(config.should_return_field("GRÖSSE", false));
+        assert!(!config.should_return_field("strase", false));
+    }
+
+    #[test]
+    fn test_instance_headers() {
+        struct Sot(BTreeMap<String, String>);
+
+        impl StructTable for Sot {
+            fn instance_headers<O: StructTableOptions>(&self, _config: &O) -> Option<Vec<String>> {
+                Some(self.0.keys().map(Into::into).collect())
+            }
+            fn data<O: StructTableOptions>(&self, _config: &O) -> Vec<Option<String>> {
+                Vec::from_iter(self.0.values().map(|x| Some(x.to_string())))
+            }
+        }
+
+        let sot = Sot(BTreeMap::from([
+            ("a".into(), "1".into()),
+            ("b".into(), "2".into()),
+            ("c".into(), "3".into()),
+        ]));
+
+        assert_eq!(
+            build_table(&sot, &OutputConfig::default()),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![
+                    vec!["a".into(), "1".into()],
+                    vec!["b".into(), "2".into()],
+                    vec!["c".into(), "3".into()]
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_dynamic() {
+        #[derive(Default, StructTable)]
+        #[structable(dynamic)]
+        struct Dynamic {
+            #[structable(title = "ID")]
+            id: u64,
+            #[structable(optional)]
+            foo: Option<String>,
+            #[structable(optional, wide)]
+            bar: Option<String>,
+        }
+
+        let config = OutputConfig {
+            wide: true,
+            ..Default::default()
+        };
+        assert_eq!(Dynamic::class_headers(&config), None);
+        assert_eq!(
+            build_table(
+                &Dynamic {
+                    id: 1,
+                    bar: Some("b".into()),
+                    ..Default::default()
+                },
+                &config
+            ),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![
+                    vec!["ID".into(), "1".into()],
+                    vec!["bar".into(), "b".into()],
+                ]
+            )
+        );
+        assert_eq!(
+            build_list_table(
+                [
+                    Dynamic {
+                        id: 1,
+                        bar: Some("b".into()),
+                        ..Default::default()
+                    },
+                    Dynamic {
+                        id: 2,
+                        foo: Some("f".into()),
+                        ..Default::default()
+                    },
+                ]
+                .iter(),
+                &config
+            ),
+            (
+                vec!["ID".into(), "bar".into(), "foo".into()],
+                vec![
+                    vec!["1".into(), "b".into(), " ".into()],
+                    vec!["2".into(), " ".into(), "f".into()],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_const_generics() {
+        #[derive(StructTable)]
+        struct Buf<const N: usize> {
+            len: usize,
+        }
+
+        #[derive(StructTable)]
+        struct TypedBuf<T: std::fmt::Display, const N: usize = 4>
+        where
+            T: Clone,
+        {
+            value: T,
+            #[structable(optional, status)]
+            state: Option<T>,
+        }
+
+        #[derive(StructTable)]
+        struct Shadow<const O: usize> {
+            len: usize,
+        }
+
+        let config = OutputConfig::default();
+        assert_eq!(
+            Shadow::<1>::class_headers(&config),
+            Some(vec!["len".into()])
+        );
+        assert_eq!(
+            build_table(&Buf::<3> { len: 3 }, &config),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![vec!["len".into(), "3".into()]]
+            )
+        );
+        let bufs: [TypedBuf<u8, 8>; 2] = [
+            TypedBuf {
+                value: 1,
+                state: Some(2),
+            },
+            TypedBuf {
+                value: 3,
+                state: None,
+            },
+        ];
+        assert_eq!(bufs[0].status(), Some("2".into()));
+        assert_eq!(
+            build_list_table(bufs.iter(), &config),
+            (
+                vec!["value".into(), "state".into()],
+                vec![vec!["1".into(), "2".into()], vec!["3".into(), " ".into()]]
+            )
+        );
+        assert_eq!(
+            TypedBuf::<String>::class_headers(&config),
+            Some(vec!["value".into(), "state".into()])
+        );
+    }
+
+    #[test]
+    fn test_lifetime_generics() {
+        #[derive(Serialize, StructTable)]
+        struct View<'a> {
+            name: &'a str,
+            #[structable(optional)]
+            description: Option<&'a str>,
+            #[structable(serialize)]
+            tags: &'a [String],
+            #[structable(optional, status)]
+            state: Option<&'a str>,
+        }
+
+        #[derive(StructTable)]
+        #[structable(dynamic)]
+        struct Nested<'a, 'b: 'a, T: std::fmt::Display + 'b> {
+            outer: &'a str,
+            #[structable(optional)]
+            inner: Option<&'b T>,
+        }
+
+        let buffer = String::from("vm1 vm2 running");
+        let words: Vec<&str> = buffer.split(' ').collect();
+        let tags = vec!["a".to_string(), "b".to_string()];
+        let views = [
+            View {
+                name: words[0],
+                description: None,
+                tags: &tags,
+                state: Some(words[2]),
+            },
+            View {
+                name: words[1],
+                description: Some("second"),
+                tags: &tags[1..],
+                state: None,
+            },
+        ];
+        let config = OutputConfig::default();
+        assert_eq!(views[0].status(), Some("running".into()));
+        assert_eq!(StructTable::status(&&views[0]), Some("running".into()));
+        assert_eq!(
+            build_list_table(views.iter(), &config),
+            (
+                vec![
+                    "name".into(),
+                    "description".into(),
+                    "tags".into(),
+                    "state".into()
+                ],
+                vec![
+                    vec![
+                        "vm1".into(),
+                        " ".into(),
+                        "[\"a\",\"b\"]".into(),
+                        "running".into()
+                    ],
+                    vec!["vm2".into(), "second".into(), "[\"b\"]".into(), " ".into()],
+                ]
+            )
+        );
+
+        let value = 42;
+        let nested = Nested {
+            outer: words[0],
+            inner: Some(&value),
+        };
+        assert_eq!(
+            build_table(&nested, &config).1,
+            vec![
+                vec!["outer".to_string(), "vm1".to_string()],
+                vec!["inner".to_string(), "42".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hide_if_empty() {
+        #[derive(StructTable)]
+        struct Data {
+            id: u64,
+            #[structable(optional, hide_if_empty, priority = 2)]
+            foo: Option<String>,
+            #[structable(hide_if_empty)]
+            bar: String,
+        }
+
+        assert_eq!(
+            Data::schema(),
+            vec![
+                FieldInfo {
+                    title: "id".into(),
+                    name: "id".into(),
+                    ..Default::default()
+                },
+                FieldInfo {
+                    title: "foo".into(),
+                    name: "foo".into(),
+                    hide_if_empty: true,
+                    priority: 2,
+                    ..Default::default()
+                },
+                FieldInfo {
+                    title: "bar".into(),
+                    name: "bar".into(),
+                    hide_if_empty: true,
+                    ..Default::default()
+                },
+            ]
+        );
+        let config = OutputConfig::default();
+        assert_eq!(
+            build_list_table(
+                [
+                    Data {
+                        id: 1,
+                        foo: None,
+                        bar: "".into()
+                    },
+                    Data {
+                        id: 2,
+                        foo: None,
+                        bar: "b".into()
+                    }
+                ]
+                .iter(),
+                &config
+            ),
+            (
+                vec!["id".into(), "bar".into()],
+                vec![vec!["1".into(), "".into()], vec!["2".into(), "b".into()]]
+            )
+        );
+        assert_eq!(
+            build_list_table(
+                [Data {
+                    id: 1,
+                    foo: None,
+                    bar: "".into()
+                }]
+                .iter(),
+                &config
+            ),
+            (vec!["id".into()], vec![vec!["1".into()]])
+        );
+    }
+
+    #[test]
+    fn test_hide_empty_columns() {
+        let config = OutputConfig {
+            hide_empty_columns: true,
+            wide: true,
+            ..Default::default()
+        };
+        let users = [
+            User {
+                id: 1,
+                first_name: "Scooby".into(),
+                ..Default::default()
+            },
+            User {
+                id: 2,
+                first_name: "John".into(),
+                last_name: " ".into(),
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            build_list_table(users.iter(), &config),
+            (
+                vec!["ID".into(), "first_name".into()],
+                vec![
+                    vec!["1".into(), "Scooby".into()],
+                    vec!["2".into(), "John".into()],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_use_serde_attrs() {
+        #[derive(Deserialize, Serialize, StructTable)]
+        #[structable(use_serde_attrs)]
+        struct Data {
+            id: u64,
+            #[serde(skip)]
+            internal: String,
+            #[serde(default, skip_serializing)]
+            secret: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            #[structable(optional)]
+            foo: Option<String>,
+        }
+
+        let data = Data {
+            id: 1,
+            internal: "i".into(),
+            secret: "s".into(),
+            foo: Some("f".into()),
+        };
+        assert_eq!((data.internal.as_str(), data.secret.as_str()), ("i", "s"));
+        assert_eq!(
+            serde_json::to_value(&data).unwrap(),
+            json!({"id": 1, "foo": "f"})
+        );
+        assert_eq!(
+            build_table(&data, &OutputConfig::default()),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![
+                    vec!["id".into(), "1".into()],
+                    vec!["foo".into(), "f".into()],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_list_results() {
+        let config = OutputConfig {
+            fields: BTreeSet::from(["ID".into()]),
+            ..Default::default()
+        };
+        let data: Vec<Result<User, String>> = vec![
+            Ok(User {
+                id: 1,
+                ..Default::default()
+            }),
+            Err("first".into()),
+            Ok(User {
+                id: 2,
+                ..Default::default()
+            }),
+            Err("second".into()),
+        ];
+
+        assert_eq!(
+            build_list_table_results(data.into_iter(), &config),
+            (
+                vec!["ID".into()],
+                vec![vec!["1".into()], vec!["2".into()]],
+                vec!["first".to_string(), "second".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn test_max_rows() {
+        let config = OutputConfig {
+            fields: BTreeSet::from(["ID".into(), "first_name".into()]),
+            max_rows: Some(2),
+            ..Default::default()
+        };
+        let users: Vec<User> = (1..=5)
+            .map(|id| User {
+                id,
+                first_name: format!("user{id}"),
+                ..Default::default()
+            })
+            .collect();
+
+        assert_eq!(
+            build_list_table(users.iter(), &config),
+            (
+                vec!["ID".into(), "first_name".into()],
+                vec![
+                    vec!["1".into(), "user1".into()],
+                    vec!["2".into(), "user2".into()],
+                    vec!["… and 3 more".into(), "".into()],
+                ]
+            )
+        );
+        assert_eq!(build_list_table(users.iter().take(2), &config).1.len(), 2);
+
+        // Entries above the limit are counted without building their rows
+        let mut built = 0;
+        let (_, rows) = build_list_table_with(users.iter(), &config, |_, row| {
+            built += 1;
+            row
+        });
+        assert_eq!(rows.len(), 3);
+        assert_eq!(built, 2);
+
+        // CSV has no trailer record
+        assert_eq!(
+            build_csv_table(users.iter(), &config, &CsvOptions::default()),
+            "ID,first_name\n1,user1\n2,user2\n3,user3\n4,user4\n5,user5\n"
+        );
+    }
+
+    #[test]
+    fn test_head_tail() {
+        let users: Vec<User> = (1..=5)
+            .map(|id| User {
+                id,
+                ..Default::default()
+            })
+            .collect();
+        let ids = |config: &OutputConfig| -> Vec<Vec<String>> {
+            build_list_table(users.iter(), config).1
+        };
+
+        let mut config = OutputConfig {
+            fields: BTreeSet::from(["ID".into()]),
+            head: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(ids(&config), vec![vec!["1"], vec!["2"]]);
+        config.head = None;
+        config.tail = Some(2);
+        assert_eq!(ids(&config), vec![vec!["4"], vec!["5"]]);
+        config.head = Some(4);
+        assert_eq!(ids(&config), vec![vec!["3"], vec!["4"]]);
+        config.tail = Some(0);
+        assert!(ids(&config).is_empty());
+    }
+
+    #[cfg(feature = "jmespath")]
+    #[test]
+    fn test_jmespath() {
+        struct CustomConfig;
+
+        impl StructTableOptions for CustomConfig {
+            fn wide_mode(&self) -> bool {
+                false
+            }
+
+            fn pretty_mode(&self) -> bool {
+                false
+            }
+
+            fn should_return_field<S: AsRef<str>>(&self, _field: S, _is_wide_field: bool) -> bool {
+                true
+            }
+
+            fn field_data_jmespath<S: AsRef<str>>(&self, field: S) -> Option<String> {
+                match field.as_ref() {
+                    "a" => Some("items[?state=='up'].name".into()),
+                    "b" => Some("length(items)".into()),
+                    _ => Some("[invalid".into()),
+                }
+            }
+        }
+
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(serialize)]
+            a: Value,
+            #[structable(optional, serialize)]
+            b: Option<Value>,
+            #[structable(serialize)]
+            c: Value,
+        }
+
+        let items =
+            json!({"items": [{"name": "x", "state": "up"}, {"name": "y", "state": "down"}]});
+        let sot = Data {
+            a: items.clone(),
+            b: Some(items.clone()),
+            c: items,
+        };
+        assert_eq!(
+            build_table(&sot, &CustomConfig),
+            (
+                vec!["Attribute".to_string(), "Value".to_string()],
+                vec![
+                    vec!["a".to_string(), "[\"x\"]".to_string()],
+                    vec!["b".to_string(), "2".to_string()],
+                    vec!["c".to_string(), "null".to_string()],
+                ]
+            ),
+        );
+    }
+
+    #[test]
+    fn test_serialization_error_placeholder() {
+        #[derive(Clone)]
+        struct Broken;
+
+        impl Serialize for Broken {
+            fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom("broken"))
+            }
+        }
+
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(serialize)]
+            a: Broken,
+            #[structable(optional, serialize)]
+            b: Option<Broken>,
+        }
+
+        let sot = Data {
+            a: Broken,
+            b: Some(Broken),
+        };
+        assert_eq!(
+            sot.data(&OutputConfig::default()),
+            vec![
+                Some(SERIALIZATION_ERROR_PLACEHOLDER.to_string()),
+                Some(SERIALIZATION_ERROR_PLACEHOLDER.to_string())
+            ]
+        );
+        assert_eq!(
+            sot.data(&OutputConfig {
+                serialization_error_placeholder: Some("-".into()),
+                ..Default::default()
+            }),
+            vec![Some("-".to_string()), Some("-".to_string())]
+        );
+
+        struct CollectingConfig(std::cell::RefCell<Vec<String>>);
+
+        impl StructTableOptions for CollectingConfig {
+            fn wide_mode(&self) -> bool {
+                false
+            }
+
+            fn pretty_mode(&self) -> bool {
+                false
+            }
+
+            fn should_return_field<S: AsRef<str>>(&self, _field: S, _is_wide_field: bool) -> bool {
+                true
+            }
+
+            fn serialization_error_placeholder<S: AsRef<str>>(
+                &self,
+                field: S,
+                error: &serde_json::Error,
+            ) -> String {
+                self.0
+                    .borrow_mut()
+                    .push(format!("{}: {}", field.as_ref(), error));
+                String::new()
+            }
+        }
+
+        let config = CollectingConfig(Default::default());
+        build_table(&sot, &config);
+        assert_eq!(
+            config.0.into_inner(),
+            vec!["a: broken".to_string(), "b: broken".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_strict() {
+        #[derive(Clone)]
+        struct Broken;
+
+        impl Serialize for Broken {
+            fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom("broken"))
+            }
+        }
+
+        #[derive(StructTable)]
+        struct Data {
+            id: u64,
+            #[structable(optional, serialize)]
+            b: Option<Broken>,
+        }
+
+        let ok = Data { id: 1, b: None };
+        let broken = Data {
+            id: 2,
+            b: Some(Broken),
+        };
+        let mut config = OutputConfig::default();
+        assert!(try_build_table(&broken, &config).is_ok());
+        assert!(try_build_list_table([&ok, &broken].into_iter(), &config).is_ok());
+
+        config.strict = true;
+        assert_eq!(try_build_table(&ok, &config), Ok(build_table(&ok, &config)));
+        let err = StructTableError::Serialization {
+            field: "b".into(),
+            message: "broken".into(),
+        };
+        assert_eq!(try_build_table(&broken, &config), Err(err.clone()));
+        assert_eq!(
+            try_build_list_table([&ok, &broken].into_iter(), &config),
+            Err(err)
+        );
+    }
+
+    #[test]
+    fn test_strict_json_pointer() {
+        struct CustomConfig;
+
+        impl StructTableOptions for CustomConfig {
+            fn wide_mode(&self) -> bool {
+                false
+            }
+
+            fn pretty_mode(&self) -> bool {
+                false
+            }
+
+            fn should_return_field<S: AsRef<str>>(&self, _field: S, _is_wide_field: bool) -> bool {
+                true
+            }
+
+            fn field_data_json_pointer<S: AsRef<str>>(&self, _field: S) -> Option<String> {
+                Some("/b".into())
+            }
+
+            fn strict_mode(&self) -> bool {
+                true
+            }
+        }
+
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(serialize)]
+            a: Value,
+        }
+
+        assert!(try_build_table(&Data { a: json!({"b": 1}) }, &CustomConfig).is_ok());
+        assert_eq!(
+            try_build_table(&Data { a: json!({"c": 1}) }, &CustomConfig),
+            Err(StructTableError::DataNotFound {
+                field: "a".into(),
+                query: "/b".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_remote() {
+        mod other {
+            pub struct Type {
+                pub id: u64,
+                pub name: Option<String>,
+            }
+        }
+
+        #[allow(dead_code)]
+        #[derive(StructTable)]
+        #[structable(remote = "other::Type")]
+        struct TypeDef {
+            #[structable(title = "ID")]
+            id: u64,
+            #[structable(optional, status)]
+            name: Option<String>,
+        }
+
+        let data = other::Type {
+            id: 1,
+            name: Some("foo".into()),
+        };
+        assert_eq!(data.status(), Some("foo".into()));
+        assert_eq!(
+            build_table(&data, &OutputConfig::default()),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![
+                    vec!["ID".into(), "1".into()],
+                    vec!["name".into(), "foo".into()],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_getter() {
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(getter = "id_str")]
+            id: u64,
+            #[structable(optional, getter = "name_upper", status)]
+            name: Option<String>,
+            #[structable(serialize, getter = "tags_sorted")]
+            tags: Vec<String>,
+        }
+
+        impl Data {
+            fn id_str(&self) -> String {
+                format!("#{}", self.id)
+            }
+            fn name_upper(&self) -> Option<String> {
+                self.name.as_ref().map(|x| x.to_uppercase())
+            }
+            fn tags_sorted(&self) -> Vec<&str> {
+                let mut tags: Vec<&str> = self.tags.iter().map(String::as_str).collect();
+                tags.sort();
+                tags
+            }
+        }
+
+        let data = Data {
+            id: 1,
+            name: Some("foo".into()),
+            tags: vec!["b".into(), "a".into()],
+        };
+        assert_eq!(data.status(), Some("FOO".into()));
+        assert_eq!(
+            build_table(&data, &OutputConfig::default()),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![
+                    vec!["id".into(), "#1".into()],
+                    vec!["name".into(), "FOO".into()],
+                    vec!["tags".into(), "[\"a\",\"b\"]".into()],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_default_wide() {
+        #[derive(Default, StructTable)]
+        #[structable(default_wide)]
+        struct Data {
+            #[structable(brief, title = "ID")]
+            id: u64,
+            #[structable(brief)]
+            name: String,
+            description: String,
+            #[structable(wide)]
+            extra: String,
+        }
+
+        assert_eq!(
+            Data::class_headers(&OutputConfig::default()),
+            Some(vec!["ID".into(), "name".into()])
+        );
+        assert_eq!(
+            Data::class_headers(&OutputConfig {
+                wide: true,
+                ..Default::default()
+            }),
+            Some(vec![
+                "ID".into(),
+                "name".into(),
+                "description".into(),
+                "extra".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_value_map() {
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(map(value = "1", to = "enabled"), map(value = "0", to = "disabled"))]
+            state: u8,
+            #[structable(optional, map(value = "a", to = "A"))]
+            opt: Option<String>,
+        }
+
+        let config = OutputConfig::default();
+        assert_eq!(
+            Data {
+                state: 1,
+                opt: Some("a".into())
+            }
+            .data(&config),
+            vec![Some("enabled".into()), Some("A".into())]
+        );
+        assert_eq!(
+            Data {
+                state: 2,
+                opt: None
+            }
+            .data(&config),
+            vec![Some("2".into()), None]
+        );
+    }
+
+    #[test]
+    fn test_thousands() {
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(thousands)]
+            size: u64,
+            #[structable(optional, thousands)]
+            count: Option<i64>,
+            id: u64,
+        }
+
+        let data = Data {
+            size: 1289347234,
+            count: Some(-12345),
+            id: 123456,
+        };
+        assert_eq!(
+            data.data(&OutputConfig::default()),
+            vec![
+                Some("1,289,347,234".into()),
+                Some("-12,345".into()),
+                Some("123456".into())
+            ]
+        );
+        assert_eq!(
+            data.data(&OutputConfig {
+                thousands_separator: Some('.'),
+                ..Default::default()
+            })[0],
+            Some("1.289.347.234".into())
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(bytes)]
+            size: u64,
+            #[structable(optional, bytes)]
+            limit: Option<u64>,
+        }
+
+        let data = Data {
+            size: 1503238554,
+            limit: Some(524288),
+        };
+        assert_eq!(
+            data.data(&OutputConfig::default()),
+            vec![Some("1.4 GiB".into()), Some("512 KiB".into())]
+        );
+        assert_eq!(
+            data.data(&OutputConfig {
+                raw_values: true,
+                ..Default::default()
+            }),
+            vec![Some("1503238554".into()), Some("524288".into())]
+        );
+    }
+
+    #[test]
+    fn test_duration() {
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(duration)]
+            uptime: u64,
+            #[structable(optional, duration)]
+            age: Option<std::time::Duration>,
+        }
+
+        let data = Data {
+            uptime: 8100,
+            age: Some(std::time::Duration::from_secs(273600)),
+        };
+        assert_eq!(
+            data.data(&OutputConfig::default()),
+            vec![Some("2h 15m".into()), Some("3d 4h".into())]
+        );
+        assert_eq!(
+            data.data(&OutputConfig {
+                raw_values: true,
+                ..Default::default()
+            }),
+            vec![Some("8100".into()), Some("273600s".into())]
+        );
+    }
+
+    #[test]
+    fn test_default_value() {
+        #[derive(StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(optional, default_value = "-")]
+            name: Option<String>,
+            #[structable(optional_default, default_value = "none")]
+            count: u32,
+        }
+
+        let data = [
+            Data {
+                id: 1,
+                name: None,
+                count: 0,
+            },
+            Data {
+                id: 2,
+                name: Some("foo".into()),
+                count: 3,
+            },
+        ];
+        assert_eq!(
+            build_list_table(data.iter(), &OutputConfig::default()).1,
+            vec![vec!["1", "-", "none"], vec!["2", "foo", "3"]]
+        );
+        assert_eq!(
+            build_table(&data[0], &OutputConfig::default()).1,
+            vec![vec!["id", "1"], vec!["name", "-"], vec!["count", "none"]]
+        );
+    }
+
+    #[test]
+    fn test_join() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            #[structable(join = " | ")]
+            tags: Vec<String>,
+            #[structable(join = ",")]
+            groups: BTreeSet<String>,
+            #[structable(optional, join = "/")]
+            path: Option<Vec<u32>>,
+            #[structable(serialize, join = "; ")]
+            names: Vec<String>,
+        }
+
+        let data = Data {
+            tags: vec!["a".into(), "b".into()],
+            groups: BTreeSet::from(["y".into(), "x".into()]),
+            path: Some(vec![1, 2]),
+            names: vec!["c".into(), "d".into()],
+        };
+        assert_eq!(
+            data.data(&OutputConfig::default()),
+            vec![
+                Some("a | b".into()),
+                Some("x,y".into()),
+                Some("1/2".into()),
+                Some("c; d".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bool_labels() {
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(bool_labels = "enabled/disabled")]
+            enabled: bool,
+            #[structable(optional, bool_labels = "yes/no")]
+            shared: Option<bool>,
+        }
+
+        let data = [
+            Data {
+                enabled: true,
+                shared: Some(false),
+            },
+            Data {
+                enabled: false,
+                shared: None,
+            },
+        ];
+        assert_eq!(
+            build_list_table(data.iter(), &OutputConfig::default()).1,
+            vec![vec!["enabled", "no"], vec!["disabled", " "]]
+        );
+    }
+
+    #[test]
+    fn test_non_clone_fields() {
+        #[derive(Serialize)]
+        struct Inner {
+            a: String,
+        }
+
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            #[structable(serialize)]
+            inner: Inner,
+            #[structable(optional, serialize)]
+            opt_inner: Option<Inner>,
+            #[structable(optional, status)]
+            state: Option<String>,
+        }
+
+        let data = Data {
+            inner: Inner { a: "x".into() },
+            opt_inner: Some(Inner { a: "y".into() }),
+            state: Some("ok".into()),
+        };
+        assert_eq!(
+            data.data(&OutputConfig::default()),
+            vec![
+                Some("{\"a\":\"x\"}".into()),
+                Some("{\"a\":\"y\"}".into()),
+                Some("ok".into())
+            ]
+        );
+        assert_eq!(data.status(), Some("ok".into()));
+    }
+
+    #[test]
+    fn test_optional_default() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(optional_default)]
+            name: String,
+            #[structable(optional_default)]
+            count: u64,
+        }
+
+        #[derive(Serialize, StructTable)]
+        #[structable(dynamic)]
+        struct DynData {
+            id: u32,
+            #[structable(optional_default)]
+            name: String,
+        }
+
+        let data = Data {
+            id: 1,
+            name: String::new(),
+            count: 3,
+        };
+        assert_eq!(
+            data.data(&OutputConfig::default()),
+            vec![Some("1".into()), None, Some("3".into())]
+        );
+        assert_eq!(
+            build_table(&data, &OutputConfig::default()).1,
+            vec![
+                vec!["id".to_string(), "1".to_string()],
+                vec!["count".to_string(), "3".to_string()],
+            ]
+        );
+
+        let data = DynData {
+            id: 1,
+            name: String::new(),
+        };
+        assert_eq!(
+            data.instance_headers(&OutputConfig::default()),
+            Some(vec!["id".to_string()])
+        );
+        assert_eq!(data.data(&OutputConfig::default()), vec![Some("1".into())]);
+    }
+
+    #[test]
+    fn test_data_with_status_serialized_once() {
+        use std::cell::Cell;
+
+        struct Counted<'a>(&'a Cell<usize>, &'static str);
+
+        impl Serialize for Counted<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.0.set(self.0.get() + 1);
+                serializer.serialize_str(self.1)
+            }
+        }
+
+        #[derive(StructTable)]
+        struct Data<'a> {
+            #[structable(key)]
+            id: u32,
+            #[structable(serialize)]
+            status: Counted<'a>,
+            #[structable(optional, serialize, title = "Power")]
+            power: Option<Counted<'a>>,
+        }
+
+        let counter = Cell::new(0);
+        let power_counter = Cell::new(0);
+        let data = Data {
+            id: 1,
+            status: Counted(&counter, "active"),
+            power: Some(Counted(&power_counter, "on")),
+        };
+        let (row, status) = data.data_with_status(&OutputConfig::default());
+        assert_eq!(
+            row,
+            vec![Some("1".into()), Some("active".into()), Some("on".into())]
+        );
+        assert_eq!(status, Some("active".into()));
+        assert_eq!(counter.get(), 1);
+        assert_eq!(power_counter.get(), 1);
+
+        assert_eq!(
+            (data.data(&OutputConfig::default()), data.status()),
+            (row, status)
+        );
+
+        // Builders serialize the status together with the row
+        counter.set(0);
+        assert_eq!(
+            status_map([&data].into_iter()),
+            BTreeMap::from([("1".to_string(), "active".to_string())])
+        );
+        assert_eq!(counter.get(), 1);
+    }
+
+    #[test]
+    fn test_null_policy() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(optional)]
+            name: Option<String>,
+        }
+
+        let data = [
+            Data { id: 1, name: None },
+            Data {
+                id: 2,
+                name: Some("b".into()),
+            },
+        ];
+        let config = OutputConfig {
+            null_policy: NullPolicy {
+                text: "-".into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            build_list_table(data.iter(), &config).1,
+            vec![
+                vec!["1".to_string(), "-".to_string()],
+                vec!["2".to_string(), "b".to_string()],
+            ]
+        );
+        assert_eq!(
+            build_csv_table(data.iter(), &config, &CsvOptions::default()),
+            "id,name\n1,\n2,b\n"
+        );
+        let config: OutputConfig =
+            serde_json::from_value(json!({"null_policy": {"csv": "NULL"}})).unwrap();
+        assert_eq!(config.null_policy.text, " ");
+        assert_eq!(
+            build_csv_table(data.iter(), &config, &CsvOptions::default()),
+            "id,name\n1,NULL\n2,b\n"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_mode() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            #[structable(thousands)]
+            size: u64,
+            #[structable(serialize)]
+            extra: Value,
+            #[structable(optional)]
+            name: Option<String>,
+        }
+
+        let data = [Data {
+            size: 1234,
+            extra: json!({"b": 1, "a": {"d": 2, "c": 3}}),
+            name: None,
+        }];
+        let config = OutputConfig {
+            thousands_separator: Some('.'),
+            null_policy: NullPolicy {
+                text: "-".into(),
+                ..Default::default()
+            },
+            serialization_error_placeholder: Some("oops".into()),
+            snapshot: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            build_list_table(data.iter(), &config).1,
+            vec![vec![
+                "1,234".to_string(),
+                "{\"a\":{\"c\":3,\"d\":2},\"b\":1}".to_string(),
+                " ".to_string()
+            ]]
+        );
+        assert_eq!(
+            config.serialization_error_placeholder(
+                "extra",
+                &<serde_json::Error as serde::ser::Error>::custom("e")
+            ),
+            SERIALIZATION_ERROR_PLACEHOLDER
+        );
+    }
+
+    #[test]
+    fn test_list_warnings() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            name: String,
+            #[structable(serialize)]
+            extra: Value,
+        }
+
+        let data = [
+            Data {
+                name: "long name".into(),
+                extra: json!({"a": 1}),
+            },
+            Data {
+                name: "short".into(),
+                extra: json!({"b": 1}),
+            },
+        ];
+        let config = OutputConfig {
+            fields: BTreeSet::from(["name".into(), "extra".into(), "missing".into()]),
+            column_widths: HashMap::from([("name".into(), 6)]),
+            ..Default::default()
+        };
+        let (headers, rows, warnings) = build_list_table_with_warnings(data.iter(), &config);
+        assert_eq!((headers, rows), build_list_table(data.iter(), &config));
+        assert_eq!(
+            warnings,
+            vec![
+                Warning::UnknownField {
+                    field: "missing".into()
+                },
+                Warning::Truncated {
+                    field: "name".into(),
+                    cells: 1
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rename() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            #[structable(title = "ID")]
+            id: u32,
+            name: String,
+        }
+
+        let data = [Data {
+            id: 1,
+            name: "Scooby".into(),
+        }];
+        let config = OutputConfig {
+            fields: BTreeSet::from(["name".into()]),
+            column_widths: HashMap::from([("name".into(), 4)]),
+            rename: HashMap::from([("Name".into(), "Nom".into())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_list_table(data.iter(), &config),
+            (vec!["Nom".into()], vec![vec!["Sco…".into()]])
+        );
+        assert_eq!(
+            build_table(&data[0], &config).1,
+            vec![vec!["Nom".to_string(), "Sco…".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_hidden_columns() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(wide)]
+            name: String,
+            #[structable(wide)]
+            extra: String,
+        }
+
+        assert_eq!(
+            Data::wide_columns(),
+            vec!["name".to_string(), "extra".to_string()]
+        );
+        assert_eq!(
+            Data::hidden_columns(&OutputConfig::default()),
+            vec!["name".to_string(), "extra".to_string()]
+        );
+        assert_eq!(
+            Data::hidden_columns(&OutputConfig {
+                fields: BTreeSet::from(["extra".into()]),
+                ..Default::default()
+            }),
+            vec!["id".to_string(), "name".to_string()]
+        );
+        assert!(<&Data>::hidden_columns(&OutputConfig {
+            wide: true,
+            ..Default::default()
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn test_class_headers_all() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(wide)]
+            name: String,
+        }
+
+        assert_eq!(
+            Data::class_headers_all(&OutputConfig::default()),
+            vec![
+                HeaderInfo {
+                    title: "id".into(),
+                    visible: true,
+                    align: Alignment::Left,
+                },
+                HeaderInfo {
+                    title: "name".into(),
+                    visible: false,
+                    align: Alignment::Left,
+                }
+            ]
+        );
+        assert!(Data::class_headers_all(&OutputConfig {
+            wide: true,
+            ..Default::default()
+        })
+        .iter()
+        .all(|hdr| hdr.visible));
+    }
+
+    #[test]
+    fn test_list_transform() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(optional)]
+            secret: Option<String>,
+        }
+
+        let data = [
+            Data {
+                id: 1,
+                secret: Some("s3cr3t".into()),
+            },
+            Data {
+                id: 2,
+                secret: None,
+            },
+        ];
+        let mut seen = 0;
+        assert_eq!(
+            build_list_table_with(data.iter(), &OutputConfig::default(), |item, mut row| {
+                seen += 1;
+                if item.secret.is_some() {
+                    row[1] = Some("***".into());
+                }
+                row
+            }),
+            (
+                vec!["id".into(), "secret".into()],
+                vec![vec!["1".into(), "***".into()], vec!["2".into(), " ".into()]]
+            )
+        );
+        assert_eq!(seen, 2);
+    }
+
+    #[test]
+    fn test_key_columns() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            #[structable(key)]
+            region: String,
+            #[structable(key, title = "ID")]
+            id: u32,
+            name: String,
+        }
+
+        assert_eq!(
+            Data::key_columns(),
+            vec!["region".to_string(), "ID".to_string()]
+        );
+        assert!(Data::schema()[1].key);
+        assert!(!Data::schema()[2].key);
+    }
+
+    #[test]
+    fn test_redact_fields() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(optional)]
+            user_data: Option<String>,
+        }
+
+        let data = [
+            Data {
+                id: 1,
+                user_data: Some("secret".into()),
+            },
+            Data {
+                id: 2,
+                user_data: None,
+            },
+        ];
+        let config = OutputConfig {
+            redact_fields: BTreeSet::from(["USER_DATA".into()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_list_table(data.iter(), &config).1,
+            vec![
+                vec!["1".to_string(), REDACTED_PLACEHOLDER.to_string()],
+                vec!["2".to_string(), " ".to_string()],
+            ]
+        );
+        assert_eq!(
+            build_table(&data[0], &config).1[1],
+            vec!["user_data".to_string(), REDACTED_PLACEHOLDER.to_string()]
+        );
+    }
+
+    #[test]
+    fn test_status_map() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            #[structable(key, wide)]
+            region: String,
+            #[structable(key, wide = 2)]
+            id: u32,
+            #[structable(optional)]
+            status: Option<String>,
+        }
+
+        let data = [
+            Data {
+                region: "r1".into(),
+                id: 1,
+                status: Some("ACTIVE".into()),
+            },
+            Data {
+                region: "r2".into(),
+                id: 1,
+                status: Some("ERROR".into()),
+            },
+            Data {
+                region: "r1".into(),
+                id: 2,
+                status: None,
+            },
+        ];
+        assert_eq!(
+            status_map(data.iter()),
+            BTreeMap::from([
+                ("r1/1".to_string(), "ACTIVE".to_string()),
+                ("r2/1".to_string(), "ERROR".to_string())
+            ])
+        );
+        assert!(status_map([User::default()].iter()).is_empty());
+    }
+
+    #[test]
+    fn test_collections() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            names: Vec<String>,
+            ports: Vec<Option<u16>>,
+            #[structable(optional)]
+            tags: Option<Vec<String>>,
+            #[structable(serialize)]
+            raw: Vec<u8>,
+        }
+
+        let data = Data {
+            names: vec!["a".into(), "b".into()],
+            ports: vec![Some(22), None, Some(80)],
+            tags: None,
+            raw: vec![1, 2],
+        };
+        assert_eq!(
+            data.data(&OutputConfig::default()),
+            vec![
+                Some("a, b".into()),
+                Some("22, 80".into()),
+                None,
+                Some("[1,2]".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extra_fields() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(wide)]
+            name: String,
+            #[structable(wide)]
+            extra: String,
+        }
+
+        let data = Data {
+            id: 1,
+            name: "a".into(),
+            extra: "b".into(),
+        };
+        let config = OutputConfig {
+            extra_fields: BTreeSet::from(["Extra".into()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            Data::class_headers(&config),
+            Some(vec!["id".into(), "extra".into()])
+        );
+        assert_eq!(
+            data.data(&OutputConfig {
+                fields: BTreeSet::from(["name".into()]),
+                ..config
+            }),
+            vec![Some("a".into()), Some("b".into())]
+        );
+    }
+
+    #[test]
+    fn test_should_return_column() {
+        struct ByName;
+
+        impl StructTableOptions for ByName {
+            fn wide_mode(&self) -> bool {
+                false
+            }
+
+            fn pretty_mode(&self) -> bool {
+                false
+            }
+
+            fn should_return_field<S: AsRef<str>>(&self, _field: S, _is_wide_field: bool) -> bool {
+                true
+            }
+
+            fn should_return_column<S: AsRef<str>>(
+                &self,
+                _title: S,
+                field_name: &str,
+                _is_wide_field: bool,
+            ) -> bool {
+                field_name != "internal_id"
+            }
+        }
+
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            #[structable(title = "ID")]
+            internal_id: u32,
+            #[structable(title = "Name")]
+            name: String,
+        }
+
+        assert_eq!(Data::class_headers(&ByName), Some(vec!["Name".into()]));
+        assert_eq!(Data::hidden_columns(&ByName), vec!["ID".to_string()]);
+        assert_eq!(Data::schema()[0].name, "internal_id");
+    }
+
+    #[test]
+    fn test_column_indexes() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(wide)]
+            name: String,
+        }
+
+        let row = Data {
+            id: 1,
+            name: "a".into(),
+        }
+        .data(&OutputConfig {
+            wide: true,
+            ..Default::default()
+        });
+        assert_eq!(row[Data::COL_ID], Some("1".into()));
+        assert_eq!(row[Data::COL_NAME], Some("a".into()));
+    }
+
+    #[test]
+    fn test_schema_example() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(example = "ACTIVE")]
+            status: String,
+        }
+
+        let schema = Data::schema();
+        assert_eq!(schema[0].example, None);
+        assert_eq!(schema[1].example.as_deref(), Some("ACTIVE"));
+    }
+
+    #[test]
+    fn test_direction() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(direction = "rtl")]
+            name: String,
+        }
+
+        let schema = Data::schema();
+        assert_eq!(schema[0].direction, TextDirection::Auto);
+        assert_eq!(schema[1].direction, TextDirection::Rtl);
+        let config = OutputConfig {
+            column_widths: HashMap::from([("name".into(), 3)]),
+            ..Default::default()
+        };
+        let data = Data {
+            id: 1,
+            name: "مرحبا".into(),
+        };
+        assert_eq!(
+            build_table(&data, &config).1[1][1],
+            "\u{2068}مر\u{2069}…".to_string()
+        );
+    }
+
+    #[test]
+    fn test_ellipsis_position() {
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            #[structable(ellipsis = "start")]
+            path: String,
+            #[structable(ellipsis = "middle")]
+            id: String,
+            name: String,
+        }
+
+        let data = [Data {
+            path: "/var/lib/data".into(),
+            id: "0123456789".into(),
+            name: "Scooby".into(),
+        }];
+        let mut config = OutputConfig {
+            column_widths: HashMap::from([
+                ("path".into(), 6),
+                ("id".into(), 5),
+                ("name".into(), 4),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_list_table(data.iter(), &config).1,
+            vec![vec!["…/data", "01…89", "Sco…"]]
+        );
+        assert_eq!(build_table(&data[0], &config).1[0][1], "…/data");
+        config.ellipsis = Some(EllipsisPosition::End);
+        assert_eq!(
+            build_list_table(data.iter(), &config).1,
+            vec![vec!["/var/…", "0123…", "Sco…"]]
+        );
+    }
+
+    #[test]
+    fn test_format_with() {
+        mod fmt {
+            pub fn size(size: &u64) -> String {
+                format!("{} KiB", size / 1024)
+            }
+        }
+
+        fn tags(tags: &[String]) -> String {
+            tags.join("|")
+        }
+
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            #[structable(format_with = "fmt::size")]
+            size: u64,
+            #[structable(optional, format_with = "fmt::size")]
+            limit: Option<u64>,
+            #[structable(format_with = "tags")]
+            tags: Vec<String>,
+        }
+
+        let data = [
+            Data {
+                size: 2048,
+                limit: Some(4096),
+                tags: vec!["a".into(), "b".into()],
+            },
+            Data {
+                size: 1024,
+                limit: None,
+                tags: Vec::new(),
+            },
+        ];
+        assert_eq!(
+            build_list_table(data.iter(), &OutputConfig::default()),
+            (
+                vec!["size".into(), "limit".into(), "tags".into()],
+                vec![
+                    vec!["2 KiB".into(), "4 KiB".into(), "a|b".into()],
+                    vec!["1 KiB".into(), " ".into(), "".into()],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_skip() {
+        #[derive(StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(skip)]
+            #[allow(dead_code)]
+            secret: String,
+            #[structable(skip, wide)]
+            #[allow(dead_code)]
+            internal: String,
+        }
+
+        let data = Data {
+            id: 1,
+            secret: "s".into(),
+            internal: "i".into(),
+        };
+        let config = OutputConfig {
+            wide: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            build_list_table([&data].into_iter(), &config),
+            (vec!["id".into()], vec![vec!["1".into()]])
+        );
+        assert_eq!(Data::schema().len(), 1);
+    }
+
+    #[test]
+    fn test_flatten() {
+        #[derive(Serialize, StructTable)]
+        struct Meta {
+            #[structable(title = "Created")]
+            created: String,
+            #[structable(wide)]
+            owner: String,
+        }
+
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(flatten)]
+            meta: Meta,
+            #[structable(flatten, optional)]
+            parent: Option<Meta>,
+            name: String,
+        }
+
+        let data = [
+            Data {
+                id: 1,
+                meta: Meta {
+                    created: "today".into(),
+                    owner: "me".into(),
+                },
+                parent: None,
+                name: "a".into(),
+            },
+            Data {
+                id: 2,
+                meta: Meta {
+                    created: "yesterday".into(),
+                    owner: "you".into(),
+                },
+                parent: Some(Meta {
+                    created: "never".into(),
+                    owner: "nobody".into(),
+                }),
+                name: "b".into(),
+            },
+        ];
+        assert_eq!(
+            build_list_table(data.iter(), &OutputConfig::default()),
+            (
+                vec![
+                    "id".into(),
+                    "Created".into(),
+                    "Created".into(),
+                    "name".into()
+                ],
+                vec![
+                    vec!["1".into(), "today".into(), " ".into(), "a".into()],
+                    vec!["2".into(), "yesterday".into(), "never".into(), "b".into()],
+                ]
+            )
+        );
+        let config = OutputConfig {
+            fields: BTreeSet::from(["id".into(), "owner".into()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_table(&data[0], &config),
             (
                 vec!["Attribute".into(), "Value".into()],
                 vec![
-                    vec!["ID".into(), "1".into()],
-                    vec!["first_name".into(), "Scooby".into()],
-                    vec!["last_name".into(), "Doo".into()],
+                    vec!["id".into(), "1".into()],
+                    vec!["owner".into(), "me".into()],
+                ]
+            )
+        );
+        assert_eq!(
+            Data::schema()
+                .into_iter()
+                .map(|field| field.title)
+                .collect::<Vec<_>>(),
+            vec!["id", "Created", "owner", "Created", "owner", "name"]
+        );
+        assert_eq!(Data::COL_ID, 0);
+    }
+
+    #[test]
+    fn test_datetime_format() {
+        /// Seconds since the epoch
+        #[derive(Serialize)]
+        struct Timestamp(i64);
+
+        impl FormatDateTime for Timestamp {
+            fn format_datetime(&self, format: &str, timezone: DisplayTimezone) -> String {
+                let offset = match timezone {
+                    DisplayTimezone::Offset(seconds) => i64::from(seconds),
+                    _ => 0,
+                };
+                format.replace("%s", &(self.0 + offset).to_string())
+            }
+        }
+
+        #[derive(Serialize, StructTable)]
+        struct Data {
+            #[structable(datetime_format = "at %s")]
+            created: Timestamp,
+            #[structable(optional, datetime_format = "at %s")]
+            deleted: Option<Timestamp>,
+        }
+
+        let data = Data {
+            created: Timestamp(10),
+            deleted: None,
+        };
+        assert_eq!(
+            build_list_table([&data].into_iter(), &OutputConfig::default()).1,
+            vec![vec!["at 10".to_string(), " ".to_string()]]
+        );
+        let config = OutputConfig {
+            display_timezone: DisplayTimezone::Offset(3600),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_table(&data, &config).1,
+            vec![vec!["created", "at 3610"]]
+        );
+    }
+
+    #[test]
+    fn test_enum() {
+        #[derive(StructTable)]
+        struct Host {
+            name: String,
+        }
+
+        #[derive(StructTable)]
+        enum Item {
+            Unknown,
+            Host(Host),
+            Disk {
+                #[structable(title = "ID")]
+                id: u32,
+                #[structable(optional)]
+                size: Option<u64>,
+                status: String,
+            },
+        }
+
+        let data = [
+            Item::Unknown,
+            Item::Host(Host { name: "a".into() }),
+            Item::Disk {
+                id: 1,
+                size: None,
+                status: "ok".into(),
+            },
+        ];
+        assert_eq!(
+            build_list_table(data.iter(), &OutputConfig::default()),
+            (
+                vec![
+                    "Item".into(),
+                    "name".into(),
+                    "ID".into(),
+                    "size".into(),
+                    "status".into()
+                ],
+                vec![
+                    vec![
+                        "Unknown".into(),
+                        " ".into(),
+                        " ".into(),
+                        " ".into(),
+                        " ".into()
+                    ],
+                    vec![" ".into(), "a".into(), " ".into(), " ".into(), " ".into()],
+                    vec![" ".into(), " ".into(), "1".into(), " ".into(), "ok".into()],
                 ]
             )
         );
+        assert_eq!(data[0].status(), None);
+        assert_eq!(data[2].status(), Some("ok".into()));
+        assert_eq!(
+            Item::schema()
+                .into_iter()
+                .map(|x| x.title)
+                .collect::<Vec<_>>(),
+            ["Item", "name", "ID", "size", "status"]
+        );
+        assert_eq!(
+            build_table(&data[2], &OutputConfig::default()).1,
+            vec![
+                vec!["ID".to_string(), "1".to_string()],
+                vec!["status".to_string(), "ok".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tuple_struct() {
+        #[derive(StructTable)]
+        struct Pair(#[structable(title = "Key")] String, u32);
+
+        #[derive(StructTable)]
+        struct Wrapper(Pair);
+
+        #[derive(StructTable)]
+        enum Item {
+            Pair(u32, #[structable(title = "Name")] String),
+        }
+
+        let data = [Pair("a".into(), 1), Pair("b".into(), 2)];
+        assert_eq!(
+            build_list_table(data.iter(), &OutputConfig::default()),
+            (
+                vec!["Key".into(), "1".into()],
+                vec![vec!["a".into(), "1".into()], vec!["b".into(), "2".into()]]
+            )
+        );
+        assert_eq!(Pair::COL_1, 1);
+        let data = [Wrapper(Pair("a".into(), 1))];
+        assert_eq!(
+            build_list_table(data.iter(), &OutputConfig::default()),
+            (
+                vec!["Key".into(), "1".into()],
+                vec![vec!["a".into(), "1".into()]]
+            )
+        );
+        let data = [Item::Pair(1, "a".into())];
+        assert_eq!(
+            build_list_table(data.iter(), &OutputConfig::default()),
+            (
+                vec!["0".into(), "Name".into()],
+                vec![vec!["1".into(), "a".into()]]
+            )
+        );
     }
 
     #[test]
-    fn test_single_wide() {
-        let config = OutputConfig {
-            wide: true,
-            ..Default::default()
-        };
-        let user = User {
+    fn test_expand() {
+        #[derive(Serialize)]
+        struct Flavor {
+            name: String,
+            vcpus: u32,
+            extra: Option<String>,
+        }
+
+        #[derive(StructTable)]
+        struct Server {
+            id: u32,
+            #[structable(expand)]
+            flavor: Flavor,
+            #[structable(expand)]
+            metadata: Value,
+        }
+
+        let data = Server {
             id: 1,
-            first_name: "Scooby".into(),
-            last_name: "Doo".into(),
-            extra: "XYZ".into(),
-            complex_data: Some(json!({"a": "b", "c": "d"})),
-            dummy: None,
+            flavor: Flavor {
+                name: "small".into(),
+                vcpus: 2,
+                extra: None,
+            },
+            metadata: json!({"a": {"b": "c"}, "d": [1, 2]}),
         };
         assert_eq!(
-            build_table(&user, &config),
-            (
-                vec!["Attribute".into(), "Value".into()],
-                vec![
-                    vec!["ID".into(), "1".into()],
-                    vec!["first_name".into(), "Scooby".into()],
-                    vec!["last_name".into(), "Doo".into()],
-                    vec!["Long".into(), "XYZ".into()],
-                    vec![
-                        "complex_data".into(),
-                        "{\"a\":\"b\",\"c\":\"d\"}".to_string()
-                    ],
-                ]
-            )
+            build_table(&data, &OutputConfig::default()).1,
+            vec![
+                vec!["id".to_string(), "1".to_string()],
+                vec!["flavor.name".to_string(), "small".to_string()],
+                vec!["flavor.vcpus".to_string(), "2".to_string()],
+                vec!["metadata.a.b".to_string(), "c".to_string()],
+                vec!["metadata.d".to_string(), "[1,2]".to_string()],
+            ]
+        );
+        assert_eq!(
+            build_list_table([data].iter(), &OutputConfig::default()).1[0][1],
+            "{\"extra\":null,\"name\":\"small\",\"vcpus\":2}"
         );
     }
 
     #[test]
-    fn test_single_wide_column() {
-        let config = OutputConfig {
-            fields: BTreeSet::from(["Long".into()]),
-            ..Default::default()
-        };
-        let user = User {
+    fn test_order() {
+        #[derive(StructTable)]
+        struct Data {
+            created: u32,
+            #[structable(order = 2)]
+            name: &'static str,
+            extra: u32,
+            #[structable(order = 1)]
+            id: u32,
+        }
+
+        let data = [Data {
+            created: 3,
+            name: "a",
+            extra: 4,
             id: 1,
-            first_name: "Scooby".into(),
-            last_name: "Doo".into(),
-            extra: "XYZ".into(),
-            complex_data: Some(json!({"a": "b", "c": "d"})),
-            dummy: None,
-        };
+        }];
         assert_eq!(
-            build_table(&user, &config),
+            build_list_table(data.iter(), &OutputConfig::default()),
             (
-                vec!["Attribute".into(), "Value".into()],
-                vec![vec!["Long".into(), "XYZ".into()],]
+                vec!["id".into(), "name".into(), "created".into(), "extra".into()],
+                vec![vec!["1".into(), "a".into(), "3".into(), "4".into()]]
             )
         );
+        assert_eq!(Data::COL_ID, 0);
+        assert_eq!(Data::COL_EXTRA, 3);
     }
 
     #[test]
-    fn test_single_wide_column_wide_mode() {
+    fn test_verbosity() {
+        #[derive(StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(wide)]
+            name: &'static str,
+            #[structable(wide = 2)]
+            extra: u32,
+        }
+
+        let data = [Data {
+            id: 1,
+            name: "a",
+            extra: 2,
+        }];
+        let headers = |config: OutputConfig| build_list_table(data.iter(), &config).0;
+        assert_eq!(headers(OutputConfig::default()), ["id"]);
         let config = OutputConfig {
-            fields: BTreeSet::from(["Long".into()]),
             wide: true,
             ..Default::default()
         };
-        let user = User {
-            id: 1,
-            first_name: "Scooby".into(),
-            last_name: "Doo".into(),
-            extra: "XYZ".into(),
-            complex_data: Some(json!({"a": "b", "c": "d"})),
-            dummy: None,
+        assert_eq!(headers(config), ["id", "name"]);
+        let config = OutputConfig {
+            verbosity: 2,
+            ..Default::default()
+        };
+        assert_eq!(headers(config), ["id", "name", "extra"]);
+        let config = OutputConfig {
+            extra_fields: BTreeSet::from(["extra".into()]),
+            ..Default::default()
         };
+        assert_eq!(headers(config), ["id", "extra"]);
         assert_eq!(
-            build_table(&user, &config),
-            (
-                vec!["Attribute".into(), "Value".into()],
-                vec![vec!["Long".into(), "XYZ".into()],]
-            )
+            Data::hidden_columns(&OutputConfig {
+                wide: true,
+                ..Default::default()
+            }),
+            ["extra"]
         );
     }
 
     #[test]
-    fn test_single_wide_pretty() {
+    fn test_count() {
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(count = "attachments")]
+            attachments: Vec<String>,
+            #[structable(count, serialize)]
+            tags: BTreeMap<String, String>,
+            #[structable(count, optional)]
+            ports: Option<Vec<u16>>,
+        }
+
+        let data = [Data {
+            attachments: vec!["a".into(), "b".into(), "c".into()],
+            tags: BTreeMap::from([("k".into(), "v".into())]),
+            ports: None,
+        }];
+        assert_eq!(
+            build_list_table(data.iter(), &OutputConfig::default()).1,
+            vec![vec!["3 attachments".to_string(), "1".into(), " ".into()]]
+        );
         let config = OutputConfig {
             wide: true,
-            pretty: true,
             ..Default::default()
         };
-        let user = User {
-            id: 1,
-            first_name: "Scooby".into(),
-            last_name: "Doo".into(),
-            extra: "XYZ".into(),
-            complex_data: Some(json!({"a": "b", "c": "d"})),
-            dummy: None,
+        assert_eq!(
+            build_list_table(data.iter(), &config).1,
+            vec![vec![
+                "a, b, c".to_string(),
+                "{\"k\":\"v\"}".into(),
+                " ".into()
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_align() {
+        #[derive(StructTable)]
+        struct Data {
+            name: String,
+            #[structable(align = "right")]
+            size: u64,
+            #[structable(align = "center", wide)]
+            state: String,
+        }
+
+        let config = OutputConfig {
+            rename: [("size".to_string(), "Size".to_string())].into(),
+            ..Default::default()
         };
+        let data = [Data {
+            name: "a".into(),
+            size: 1,
+            state: "ok".into(),
+        }];
+        let (headers, _) = build_list_table(data.iter(), &config);
         assert_eq!(
-            build_table(&user, &config),
-            (
-                vec!["Attribute".into(), "Value".into()],
-                vec![
-                    vec!["ID".into(), "1".into()],
-                    vec!["first_name".into(), "Scooby".into()],
-                    vec!["last_name".into(), "Doo".into()],
-                    vec!["Long".into(), "XYZ".into()],
-                    vec![
-                        "complex_data".into(),
-                        "{\n  \"a\": \"b\",\n  \"c\": \"d\"\n}".to_string()
-                    ],
-                ]
-            )
+            Data::column_alignments(&headers, &config),
+            [Alignment::Left, Alignment::Right]
+        );
+        assert_eq!(
+            Data::class_headers_all(&config)
+                .into_iter()
+                .map(|hdr| hdr.align)
+                .collect::<Vec<_>>(),
+            [Alignment::Left, Alignment::Right, Alignment::Center]
         );
     }
 
     #[test]
-    fn test_single_status() {
+    fn test_style() {
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(style = "bold")]
+            id: u32,
+            #[structable(color = "red")]
+            state: String,
+            name: String,
+        }
+
+        let headers = vec!["id".to_string(), "state".into(), "extra".into()];
         assert_eq!(
-            StatusStruct {
-                status: "foo".into(),
-            }
-            .status(),
-            Some("foo".into())
+            Data::column_styles(&headers, &OutputConfig::default()),
+            [
+                CellStyle {
+                    style: Some("bold".into()),
+                    color: None
+                },
+                CellStyle {
+                    style: None,
+                    color: Some("red".into())
+                },
+                CellStyle::default()
+            ]
         );
     }
 
     #[test]
-    fn test_single_no_status() {
-        assert_eq!(User::default().status(), None);
+    fn test_link() {
+        #[derive(StructTable)]
+        struct Server {
+            #[structable(link = "https://dashboard/{region}/servers/{id}")]
+            id: u32,
+            #[structable(skip)]
+            region: &'static str,
+            name: &'static str,
+        }
+
+        let data = [Server {
+            id: 1,
+            region: "eu",
+            name: "a",
+        }];
+        assert_eq!(
+            data[0].links(),
+            BTreeMap::from([(
+                "id".to_string(),
+                "https://dashboard/eu/servers/1".to_string()
+            )])
+        );
+        assert_eq!(
+            build_list_table(data.iter(), &OutputConfig::default()).1,
+            vec![vec!["1".to_string(), "a".into()]]
+        );
+        let config = OutputConfig {
+            hyperlinks: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            build_list_table(data.iter(), &config).1,
+            vec![vec![
+                "\x1b]8;;https://dashboard/eu/servers/1\x1b\\1\x1b]8;;\x1b\\".to_string(),
+                "a".into()
+            ]]
+        );
+
+        // Visible text is truncated before the hyperlink is applied
+        #[derive(StructTable)]
+        struct Named {
+            #[structable(link = "https://dashboard/{name}", max_length = 4)]
+            name: &'static str,
+            #[structable(link = "https://dashboard/{name}/{id}")]
+            id: &'static str,
+        }
+
+        let data = [Named {
+            name: "long-name",
+            id: "identifier",
+        }];
+        let config = OutputConfig {
+            hyperlinks: true,
+            column_widths: HashMap::from([("id".to_string(), 5)]),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_list_table(data.iter(), &config).1,
+            vec![vec![
+                "\x1b]8;;https://dashboard/long-name\x1b\\lon…\x1b]8;;\x1b\\".to_string(),
+                "\x1b]8;;https://dashboard/long-name/identifier\x1b\\iden…\x1b]8;;\x1b\\".into()
+            ]]
+        );
+        assert_eq!(
+            build_table(&data[0], &config).1,
+            vec![
+                vec![
+                    "name".to_string(),
+                    "\x1b]8;;https://dashboard/long-name\x1b\\lon…\x1b]8;;\x1b\\".into()
+                ],
+                vec![
+                    "id".to_string(),
+                    "\x1b]8;;https://dashboard/long-name/identifier\x1b\\iden…\x1b]8;;\x1b\\"
+                        .into()
+                ],
+            ]
+        );
     }
 
     #[test]
-    fn test_single_option_status() {
+    fn test_link_placeholders() {
+        #[derive(StructTable)]
+        struct Pair(
+            u32,
+            #[structable(link = "https://x/{0}/{1}")]
+            &'static str,
+        );
+
+        #[derive(StructTable)]
+        struct Resource {
+            #[structable(link = "https://x/{type}/{id:03}")]
+            id: u32,
+            r#type: &'static str,
+        }
+
         assert_eq!(
-            OptionStatusStruct {
-                status: Some("foo".into()),
+            Pair(1, "a").links(),
+            BTreeMap::from([("1".to_string(), "https://x/1/a".to_string())])
+        );
+        assert_eq!(
+            Resource {
+                id: 7,
+                r#type: "server"
             }
-            .status(),
-            Some("foo".into())
+            .links(),
+            BTreeMap::from([("id".to_string(), "https://x/server/007".to_string())])
         );
     }
 
     #[test]
-    fn test_complex_status() {
+    fn test_statuses() {
+        #[derive(Serialize, StructTable)]
+        struct Server {
+            id: u32,
+            #[structable(status)]
+            status: String,
+            #[structable(status, optional)]
+            power_state: Option<String>,
+            #[structable(status, serialize)]
+            health: Value,
+        }
+
+        let data = Server {
+            id: 1,
+            status: "ACTIVE".into(),
+            power_state: None,
+            health: json!("ok"),
+        };
+        assert_eq!(data.status(), Some("ACTIVE".into()));
         assert_eq!(
-            SerializeStatusStruct {
-                status: Status::Dummy,
-            }
-            .status(),
-            Some("Dummy".into())
+            data.statuses(),
+            [
+                ("status".to_string(), "ACTIVE".to_string()),
+                ("health".to_string(), "ok".to_string())
+            ]
         );
 
+        #[derive(StructTable)]
+        struct Plain {
+            #[structable(title = "Status")]
+            state: String,
+        }
         assert_eq!(
-            SerializeOptionStatusStruct {
-                status: Some(Status::Dummy),
-            }
-            .status(),
-            Some("Dummy".into())
+            Plain { state: "up".into() }.statuses(),
+            [("Status".to_string(), "up".to_string())]
         );
+    }
 
-        let (_, rows) = build_table(
-            &SerializeOptionStatusStruct {
-                status: Some(Status::Dummy),
-            },
-            &OutputConfig::default(),
+    #[test]
+    fn test_unit() {
+        #[derive(StructTable)]
+        struct Flavor {
+            #[structable(unit = "MB", thousands)]
+            ram: u64,
+            #[structable(unit = "GB", optional)]
+            disk: Option<u64>,
+        }
+
+        let data = [Flavor {
+            ram: 2048,
+            disk: None,
+        }];
+        assert_eq!(
+            build_list_table(data.iter(), &OutputConfig::default()).1,
+            vec![vec!["2,048 MB".to_string(), " ".into()]]
         );
-        assert_eq!(vec![vec!["status".to_string(), "Dummy".to_string()]], rows);
+        let config = OutputConfig {
+            raw_values: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            build_list_table(data.iter(), &config).1,
+            vec![vec!["2,048".to_string(), " ".into()]]
+        );
+        assert_eq!(Flavor::schema()[1].unit.as_deref(), Some("GB"));
+    }
 
-        let (_, rows) = build_list_table(
-            [SerializeOptionStatusStruct {
-                status: Some(Status::Dummy),
-            }]
-            .iter(),
-            &OutputConfig::default(),
+    #[test]
+    fn test_serialize_with() {
+        fn strip_nulls<S: serde::Serializer>(
+            value: &Value,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Value::Object(map) => {
+                    serializer.collect_map(map.iter().filter(|(_, v)| !v.is_null()))
+                }
+                other => other.serialize(serializer),
+            }
+        }
+
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(serialize_with = "strip_nulls")]
+            meta: Value,
+            #[structable(serialize_with = "strip_nulls", optional, status)]
+            extra: Option<Value>,
+        }
+
+        let data = Data {
+            meta: json!({"a": 1, "b": null}),
+            extra: Some(json!({"c": null})),
+        };
+        assert_eq!(
+            data.data(&OutputConfig::default()),
+            [Some("{\"a\":1}".to_string()), Some("{}".to_string())]
         );
-        assert_eq!(vec![vec!["Dummy".to_string()]], rows);
+        assert_eq!(data.status(), Some("{}".to_string()));
     }
 
     #[test]
-    fn test_status() {
-        #[derive(Deserialize, Serialize, StructTable)]
-        struct StatusStruct {
-            #[structable(title = "ID")]
-            id: u64,
-            #[structable(status)]
-            status: String,
+    fn test_max_length() {
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(max_length = 8)]
+            description: &'static str,
         }
+
+        let data = [Data {
+            description: "long description",
+        }];
+        assert_eq!(
+            build_list_table(data.iter(), &OutputConfig::default()).1,
+            vec![vec!["long de…".to_string()]]
+        );
+        assert_eq!(
+            build_table(&data[0], &OutputConfig::default()).1,
+            vec![vec!["description".to_string(), "long de…".to_string()]]
+        );
+        let config = OutputConfig {
+            wide: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            build_list_table(data.iter(), &config).1,
+            vec![vec!["long description".to_string()]]
+        );
+        let config = OutputConfig {
+            column_widths: HashMap::from([("description".into(), 5)]),
+            wide: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            build_list_table(data.iter(), &config).1,
+            vec![vec!["long…".to_string()]]
+        );
     }
 
     #[test]
-    fn test_list() {
-        let config = OutputConfig::default();
-        let users = vec![
-            User {
-                id: 1,
-                first_name: "Scooby".into(),
-                last_name: "Doo".into(),
-                extra: "Foo".into(),
-                complex_data: Some(json!({"a": "b", "c": "d"})),
-                dummy: None,
-            },
-            User {
-                id: 2,
-                first_name: "John".into(),
-                last_name: "Cena".into(),
-                extra: "Bar".into(),
-                complex_data: None,
-                dummy: None,
-            },
-        ];
+    fn test_case() {
+        #[derive(StructTable)]
+        struct Data {
+            #[structable(uppercase)]
+            region: &'static str,
+            #[structable(uppercase, map(value = "active", to = "up"))]
+            state: &'static str,
+            #[structable(titlecase, optional)]
+            power: Option<&'static str>,
+            #[structable(uppercase, bool_labels = "yes/no")]
+            enabled: bool,
+        }
 
+        let data = [Data {
+            region: "eu-de",
+            state: "active",
+            power: Some("RUNNING"),
+            enabled: true,
+        }];
         assert_eq!(
-            build_list_table(users.iter(), &config),
-            (
-                vec![
-                    "ID".into(),
-                    "first_name".into(),
-                    "last_name".into(),
-                    "dummy".into()
-                ],
-                vec![
-                    vec!["1".into(), "Scooby".into(), "Doo".into(), " ".into()],
-                    vec!["2".into(), "John".into(), "Cena".into(), " ".into()],
-                ]
-            )
+            build_list_table(data.iter(), &OutputConfig::default()).1,
+            vec![vec![
+                "EU-DE".to_string(),
+                "UP".into(),
+                "Running".into(),
+                "YES".into()
+            ]]
         );
     }
 
     #[test]
-    fn test_list_wide_column() {
-        let config = OutputConfig {
-            fields: BTreeSet::from(["Long".into()]),
-            ..Default::default()
-        };
-        let users = vec![
-            User {
-                id: 1,
-                first_name: "Scooby".into(),
-                last_name: "Doo".into(),
-                extra: "Foo".into(),
-                complex_data: Some(json!({"a": "b", "c": "d"})),
-                dummy: None,
-            },
-            User {
-                id: 2,
-                first_name: "John".into(),
-                last_name: "Cena".into(),
-                extra: "Bar".into(),
-                complex_data: None,
-                dummy: Some("foo".into()),
-            },
-        ];
+    fn test_skip_if_empty() {
+        #[derive(StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(skip_if_empty)]
+            description: String,
+            #[structable(skip_if_empty)]
+            tags: Vec<String>,
+            #[structable(skip_if_empty, serialize)]
+            metadata: Value,
+            #[structable(skip_if_empty, default_value = "-")]
+            name: String,
+            #[structable(skip_if_empty, serialize)]
+            ports: Vec<u16>,
+            #[structable(skip_if_empty)]
+            pattern: String,
+        }
 
+        let data = [Data {
+            id: 1,
+            description: String::new(),
+            tags: Vec::new(),
+            metadata: json!({}),
+            name: String::new(),
+            ports: Vec::new(),
+            pattern: "[]".into(),
+        }];
         assert_eq!(
-            build_list_table(users.iter(), &config),
-            (
-                vec!["Long".into(),],
-                vec![vec!["Foo".into(),], vec!["Bar".into(),],]
-            )
+            build_list_table(data.iter(), &OutputConfig::default()).1,
+            vec![vec![
+                "1".to_string(),
+                " ".into(),
+                " ".into(),
+                " ".into(),
+                "-".into(),
+                " ".into(),
+                "[]".into()
+            ]]
+        );
+        assert_eq!(
+            build_table(&data[0], &OutputConfig::default()).1,
+            vec![
+                vec!["id".to_string(), "1".to_string()],
+                vec!["name".to_string(), "-".to_string()],
+                vec!["pattern".to_string(), "[]".to_string()]
+            ]
         );
     }
 
     #[test]
-    fn test_list_wide_column_wide_mode() {
-        let config = OutputConfig {
-            fields: BTreeSet::from(["Long".into()]),
-            wide: true,
-            pretty: false,
-        };
-        let users = vec![
-            User {
-                id: 1,
-                first_name: "Scooby".into(),
-                last_name: "Doo".into(),
-                extra: "Foo".into(),
-                complex_data: Some(json!({"a": "b", "c": "d"})),
-                dummy: None,
-            },
-            User {
-                id: 2,
-                first_name: "John".into(),
-                last_name: "Cena".into(),
-                extra: "Bar".into(),
-                complex_data: None,
-                dummy: Some("foo".into()),
-            },
-        ];
+    fn test_compute() {
+        #[derive(StructTable)]
+        #[structable(
+            compute(title = "Usage", method = "usage"),
+            compute(title = "Free", method = "free", wide)
+        )]
+        struct Quota {
+            used: u32,
+            total: u32,
+        }
+
+        impl Quota {
+            fn usage(&self) -> String {
+                format!("{}/{}", self.used, self.total)
+            }
 
+            fn free(&self) -> u32 {
+                self.total - self.used
+            }
+        }
+
+        let data = [Quota { used: 2, total: 10 }];
         assert_eq!(
-            build_list_table(users.iter(), &config),
+            build_list_table(data.iter(), &OutputConfig::default()),
             (
-                vec!["Long".into(),],
-                vec![vec!["Foo".into(),], vec!["Bar".into(),],]
+                vec!["used".into(), "total".into(), "Usage".into()],
+                vec![vec!["2".into(), "10".into(), "2/10".into()]]
             )
         );
+        let config = OutputConfig {
+            wide: true,
+            ..Default::default()
+        };
+        assert_eq!(build_list_table(data.iter(), &config).1[0][3], "8");
+        assert_eq!(Quota::wide_columns(), ["Free"]);
     }
 
     #[test]
-    fn test_list_wide() {
-        let config = OutputConfig {
-            fields: BTreeSet::new(),
-            wide: true,
-            pretty: false,
+    fn test_serde_rename() {
+        #[derive(Serialize, StructTable)]
+        #[structable(use_serde_attrs)]
+        struct Data {
+            #[serde(rename = "ID")]
+            id: u64,
+            #[serde(rename(serialize = "Name", deserialize = "name"))]
+            name: String,
+            #[serde(rename = "Ignored")]
+            #[structable(title = "Description")]
+            description: String,
+        }
+
+        #[derive(Serialize, StructTable)]
+        struct Plain {
+            #[serde(rename = "ID")]
+            id: u64,
+        }
+
+        let data = Data {
+            id: 1,
+            name: "a".into(),
+            description: "b".into(),
         };
-        let users = vec![
-            User {
+        assert_eq!(
+            Data::class_headers(&OutputConfig::default()),
+            Some(vec!["ID".into(), "Name".into(), "Description".into()])
+        );
+        assert_eq!(data.data(&OutputConfig::default())[0], Some("1".into()));
+        assert_eq!(
+            Plain::class_headers(&OutputConfig::default()),
+            Some(vec!["id".into()])
+        );
+    }
+
+    #[test]
+    fn test_spread() {
+        #[derive(StructTable)]
+        struct Data {
+            id: u32,
+            #[structable(spread)]
+            labels: HashMap<String, String>,
+            #[structable(spread, serialize)]
+            extra: BTreeMap<String, Value>,
+        }
+
+        let data = [
+            Data {
                 id: 1,
-                first_name: "Scooby".into(),
-                last_name: "Doo".into(),
-                extra: "Foo".into(),
-                complex_data: Some(json!({"a": "b", "c": "d"})),
-                dummy: None,
+                labels: HashMap::from([
+                    ("zone".to_string(), "a".to_string()),
+                    ("env".to_string(), "prod".to_string()),
+                ]),
+                extra: BTreeMap::from([("size".to_string(), json!(2))]),
             },
-            User {
+            Data {
                 id: 2,
-                first_name: "John".into(),
-                last_name: "Cena".into(),
-                extra: "Bar".into(),
-                complex_data: None,
-                dummy: Some("foo".into()),
+                labels: HashMap::from([("owner".to_string(), "me".to_string())]),
+                extra: BTreeMap::new(),
             },
         ];
-
+        assert!(Data::class_headers(&OutputConfig::default()).is_none());
         assert_eq!(
-            build_list_table(users.iter(), &config),
+            build_list_table(data.iter(), &OutputConfig::default()),
             (
                 vec![
-                    "ID".into(),
-                    "first_name".into(),
-                    "last_name".into(),
-                    "Long".into(),
-                    "complex_data".into(),
-                    "dummy".into()
+                    "id".to_string(),
+                    "env".into(),
+                    "zone".into(),
+                    "size".into(),
+                    "owner".into()
                 ],
                 vec![
                     vec![
-                        "1".into(),
-                        "Scooby".into(),
-                        "Doo".into(),
-                        "Foo".into(),
-                        "{\"a\":\"b\",\"c\":\"d\"}".to_string(),
-                        " ".to_string()
+                        "1".to_string(),
+                        "prod".into(),
+                        "a".into(),
+                        "2".into(),
+                        " ".into()
                     ],
                     vec![
-                        "2".into(),
-                        "John".into(),
-                        "Cena".into(),
-                        "Bar".into(),
-                        " ".to_string(),
-                        "foo".into()
+                        "2".to_string(),
+                        " ".into(),
+                        " ".into(),
+                        " ".into(),
+                        "me".into()
                     ],
                 ]
             )
         );
+        let config = OutputConfig {
+            fields: BTreeSet::from(["id".to_string(), "zone".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_table(&data[0], &config).1,
+            vec![
+                vec!["id".to_string(), "1".to_string()],
+                vec!["zone".to_string(), "a".to_string()]
+            ]
+        );
     }
 
     #[test]
-    fn test_deser() {
-        #[derive(Deserialize, Serialize, StructTable)]
-        struct Foo {
-            #[structable(title = "ID")]
-            id: u64,
-            #[structable(optional)]
-            foo: Option<String>,
-            #[structable(optional)]
-            bar: Option<bool>,
+    fn test_inner() {
+        #[derive(StructTable)]
+        struct Paginated<T: StructTable> {
+            #[structable(inner)]
+            items: T,
+            #[allow(dead_code)]
+            marker: Option<String>,
         }
 
-        let foo: Foo = serde_json::from_value(json!({"id": 1})).expect("Foo object");
+        #[derive(StructTable)]
+        struct Dynamic {
+            #[structable(spread)]
+            labels: BTreeMap<String, String>,
+        }
 
+        let data = Paginated {
+            items: User {
+                id: 1,
+                first_name: "Scooby".into(),
+                last_name: "Doo".into(),
+                ..Default::default()
+            },
+            marker: None,
+        };
         assert_eq!(
-            build_table(&foo, &OutputConfig::default()),
-            (
-                vec!["Attribute".into(), "Value".into()],
-                vec![vec!["ID".into(), "1".into()],]
-            )
+            Paginated::<User>::class_headers(&OutputConfig::default()),
+            User::class_headers(&OutputConfig::default())
+        );
+        assert_eq!(
+            build_table(&data, &OutputConfig::default()),
+            build_table(&data.items, &OutputConfig::default())
+        );
+        assert_eq!(Paginated::<User>::schema().len(), User::schema().len());
+
+        let data = Paginated {
+            items: StatusStruct {
+                status: "active".into(),
+            },
+            marker: None,
+        };
+        assert_eq!(data.status(), Some("active".into()));
+
+        let data = Paginated {
+            items: Dynamic {
+                labels: BTreeMap::from([("env".to_string(), "prod".to_string())]),
+            },
+            marker: Some("next".into()),
+        };
+        assert_eq!(
+            build_table(&data, &OutputConfig::default()).1,
+            vec![vec!["env".to_string(), "prod".to_string()]]
         );
     }
 
     #[test]
-    fn test_output_config() {
+    fn test_tags() {
+        #[derive(StructTable)]
+        struct Server {
+            id: u32,
+            name: String,
+            #[structable(tags = "network")]
+            address: String,
+            #[structable(tags = "network, detail", wide)]
+            mac: String,
+            #[structable(tags = "detail")]
+            flavor: String,
+        }
+
+        let data = Server {
+            id: 1,
+            name: "srv".into(),
+            address: "10.0.0.1".into(),
+            mac: "aa:bb".into(),
+            flavor: "small".into(),
+        };
+        assert_eq!(
+            Server::class_headers(&OutputConfig::default()),
+            Some(vec![
+                "id".to_string(),
+                "name".into(),
+                "address".into(),
+                "flavor".into()
+            ])
+        );
+        let config: OutputConfig = "fields=id,@network".parse().unwrap();
+        assert_eq!(
+            build_table(&data, &config).1,
+            vec![
+                vec!["id".to_string(), "1".to_string()],
+                vec!["address".to_string(), "10.0.0.1".to_string()],
+                vec!["mac".to_string(), "aa:bb".to_string()]
+            ]
+        );
         let config = OutputConfig {
-            fields: BTreeSet::from(["Foo".into(), "bAr".into(), "BAZ".into(), "a:b-c".into()]),
+            groups: BTreeSet::from(["Detail".to_string()]),
             ..Default::default()
         };
-
-        assert!(config.should_return_field("Foo", false));
-        assert!(config.should_return_field("FOO", false));
-        assert!(config.should_return_field("bar", false));
-        assert!(config.should_return_field("baz", false));
-        assert!(config.should_return_field("a:b-c", false));
+        assert_eq!(
+            Server::class_headers(&config),
+            Some(vec!["mac".to_string(), "flavor".into()])
+        );
+        assert_eq!(
+            Server::class_headers_all(&config)
+                .into_iter()
+                .filter(|hdr| hdr.visible)
+                .map(|hdr| hdr.title)
+                .collect::<Vec<_>>(),
+            vec!["mac".to_string(), "flavor".into()]
+        );
+        assert_eq!(
+            Server::hidden_columns(&config),
+            vec!["id".to_string(), "name".into(), "address".into()]
+        );
+        assert_eq!(
+            Server::schema()
+                .into_iter()
+                .map(|x| x.tags)
+                .collect::<Vec<_>>(),
+            vec![
+                vec![],
+                vec![],
+                vec!["network".to_string()],
+                vec!["network".to_string(), "detail".into()],
+                vec!["detail".to_string()]
+            ]
+        );
     }
 
     #[test]
-    fn test_instance_headers() {
-        struct Sot(BTreeMap<String, String>);
-
-        impl StructTable for Sot {
-            fn instance_headers<O: StructTableOptions>(&self, _config: &O) -> Option<Vec<String>> {
-                Some(self.0.keys().map(Into::into).collect())
-            }
-            fn data<O: StructTableOptions>(&self, _config: &O) -> Vec<Option<String>> {
-                Vec::from_iter(self.0.values().map(|x| Some(x.to_string())))
-            }
+    fn test_field_descriptions() {
+        #[derive(StructTable)]
+        struct Server {
+            /// Server ID
+            #[structable(title = "ID")]
+            id: u32,
+            name: String,
+            /// Current state of the server.
+            ///
+            /// Either `ACTIVE` or `ERROR`.
+            status: String,
         }
 
-        let sot = Sot(BTreeMap::from([
-            ("a".into(), "1".into()),
-            ("b".into(), "2".into()),
-            ("c".into(), "3".into()),
-        ]));
-
         assert_eq!(
-            build_table(&sot, &OutputConfig::default()),
-            (
-                vec!["Attribute".into(), "Value".into()],
-                vec![
-                    vec!["a".into(), "1".into()],
-                    vec!["b".into(), "2".into()],
-                    vec!["c".into(), "3".into()]
-                ]
-            )
+            Server::field_descriptions(),
+            vec![
+                ("ID".to_string(), "Server ID".to_string()),
+                (
+                    "status".to_string(),
+                    "Current state of the server.\n\nEither `ACTIVE` or `ERROR`.".to_string()
+                )
+            ]
         );
+        assert_eq!(<&Server as StructTable>::schema()[1].description, None);
     }
 
     #[test]
@@ -0,0 +1,96 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Progress reporting of the list table build
+
+use crate::{build_list_table_with, StructTable, StructTableOptions};
+
+/// Receiver of the table build progress
+pub trait ProgressSink {
+    /// Advance the progress by the `delta` processed entries
+    fn inc(&self, delta: u64);
+}
+
+#[cfg(feature = "indicatif")]
+impl ProgressSink for indicatif::ProgressBar {
+    fn inc(&self, delta: u64) {
+        indicatif::ProgressBar::inc(self, delta)
+    }
+}
+
+/// Build a table for list of entries reporting every processed entry to the `progress`
+///
+/// Same as [`build_list_table`](crate::build_list_table). With the `indicatif` feature the
+/// `indicatif::ProgressBar` can be used as the progress sink directly.
+pub fn build_list_table_with_progress<I, T, O, P>(
+    data: I,
+    options: &O,
+    progress: &P,
+) -> (Vec<String>, Vec<Vec<String>>)
+where
+    I: Iterator<Item = T>,
+    T: StructTable,
+    O: StructTableOptions,
+    P: ProgressSink + ?Sized,
+{
+    build_list_table_with(data, options, |_: &T, row| {
+        progress.inc(1);
+        row
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::{build_list_table, OutputConfig};
+
+    #[derive(StructTable)]
+    struct Data {
+        id: u32,
+    }
+
+    struct Counter(Cell<u64>);
+
+    impl ProgressSink for Counter {
+        fn inc(&self, delta: u64) {
+            self.0.set(self.0.get() + delta);
+        }
+    }
+
+    #[test]
+    fn test_progress() {
+        let data = [Data { id: 1 }, Data { id: 2 }, Data { id: 3 }];
+        let config = OutputConfig {
+            head: Some(2),
+            ..Default::default()
+        };
+        let counter = Counter(Cell::new(0));
+        assert_eq!(
+            build_list_table_with_progress(data.iter(), &config, &counter),
+            build_list_table(data.iter(), &config)
+        );
+        assert_eq!(counter.0.get(), 2);
+    }
+
+    #[cfg(feature = "indicatif")]
+    #[test]
+    fn test_indicatif() {
+        let data = [Data { id: 1 }, Data { id: 2 }];
+        let bar = indicatif::ProgressBar::hidden();
+        build_list_table_with_progress(data.iter(), &OutputConfig::default(), &bar);
+        assert_eq!(bar.position(), 2);
+    }
+}
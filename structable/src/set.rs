@@ -0,0 +1,81 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sets of related tables
+
+use crate::{build_list_table, build_table, StructTable, StructTableOptions};
+
+/// Master–detail set of tables
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableSet {
+    /// Attribute/Value table of the parent item as returned by [`build_table`]
+    pub parent: (Vec<String>, Vec<Vec<String>>),
+    /// List table of the child items as returned by [`build_list_table`]
+    pub children: (Vec<String>, Vec<Vec<String>>),
+}
+
+/// Build the master–detail set of tables (i.e. a server and its volumes)
+///
+/// Both the parent and the children tables are built with the same options.
+pub fn build_table_set<P, I, T, O>(parent: &P, children: I, options: &O) -> TableSet
+where
+    P: StructTable,
+    I: Iterator<Item = T>,
+    T: StructTable,
+    O: StructTableOptions,
+{
+    TableSet {
+        parent: build_table(parent, options),
+        children: build_list_table(children, options),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OutputConfig;
+
+    #[derive(StructTable)]
+    struct Server {
+        name: &'static str,
+    }
+
+    #[derive(StructTable)]
+    struct Volume {
+        id: u32,
+        size: u32,
+    }
+
+    #[test]
+    fn test_table_set() {
+        let volumes = [Volume { id: 1, size: 10 }, Volume { id: 2, size: 20 }];
+        assert_eq!(
+            build_table_set(
+                &Server { name: "srv" },
+                volumes.iter(),
+                &OutputConfig::default()
+            ),
+            TableSet {
+                parent: (
+                    vec!["Attribute".into(), "Value".into()],
+                    vec![vec!["name".into(), "srv".into()]]
+                ),
+                children: (
+                    vec!["id".into(), "size".into()],
+                    vec![vec!["1".into(), "10".into()], vec!["2".into(), "20".into()]]
+                ),
+            }
+        );
+    }
+}
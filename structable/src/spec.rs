@@ -0,0 +1,232 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runtime table specification applied to the `serde_json::Value` data
+//!
+//! This allows defining tables in the configuration (i.e. TOML or JSON files) without the derive
+//! macro:
+//!
+//! ```rust
+//! # use serde_json::json;
+//! use structable::{OutputConfig, TableSpec};
+//!
+//! let spec: TableSpec = serde_json::from_value(json!({
+//!     "columns": [
+//!         {"title": "ID", "pointer": "/id"},
+//!         {"title": "Name", "pointer": "/name"},
+//!         {"title": "Tags", "pointer": "/tags", "wide": true}
+//!     ]
+//! })).unwrap();
+//! let data = json!({"id": 1, "name": "foo", "tags": ["a", "b"]});
+//! let (_, rows) = spec.build_table(&data, &OutputConfig::default());
+//! assert_eq!(rows, vec![vec!["ID", "1"], vec!["Name", "foo"]]);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{build_list_table, build_table, StructTable, StructTableOptions};
+
+/// Format of the column value
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnFormat {
+    /// Strings are returned as is, other values are serialized to json
+    #[default]
+    Text,
+    /// Value is always serialized to json (pretty in the `pretty` mode)
+    Json,
+}
+
+/// Specification of the table column
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ColumnSpec {
+    /// Column title
+    pub title: String,
+    /// Json pointer of the column data [RFC](https://datatracker.ietf.org/doc/html/rfc6901)
+    pub pointer: String,
+    /// Whether the column is returned in the wide mode only
+    #[serde(default)]
+    pub wide: bool,
+    /// Format of the column value
+    #[serde(default)]
+    pub format: ColumnFormat,
+}
+
+/// Runtime table specification
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct TableSpec {
+    /// Table columns
+    pub columns: Vec<ColumnSpec>,
+}
+
+/// Data bound to the table specification
+pub struct SpecItem<'a> {
+    spec: &'a TableSpec,
+    value: &'a Value,
+}
+
+impl TableSpec {
+    /// Bind the data to the specification to be used with the [`StructTable`] based functions
+    pub fn bind<'a>(&'a self, value: &'a Value) -> SpecItem<'a> {
+        SpecItem { spec: self, value }
+    }
+
+    /// Build a table for a single value (see [`build_table`])
+    pub fn build_table<O: StructTableOptions>(
+        &self,
+        value: &Value,
+        options: &O,
+    ) -> (Vec<String>, Vec<Vec<String>>) {
+        build_table(&self.bind(value), options)
+    }
+
+    /// Build a table for list of values (see [`build_list_table`])
+    pub fn build_list_table<'a, I, O>(
+        &self,
+        values: I,
+        options: &O,
+    ) -> (Vec<String>, Vec<Vec<String>>)
+    where
+        I: Iterator<Item = &'a Value>,
+        O: StructTableOptions,
+    {
+        build_list_table(values.map(|value| self.bind(value)), options)
+    }
+
+    /// Columns selected by the options
+    fn selected_columns<'a, O: StructTableOptions>(
+        &'a self,
+        options: &'a O,
+    ) -> impl Iterator<Item = &'a ColumnSpec> {
+        self.columns
+            .iter()
+            .filter(|col| options.should_return_field(&col.title, col.wide))
+    }
+}
+
+impl StructTable for SpecItem<'_> {
+    fn instance_headers<O: StructTableOptions>(&self, options: &O) -> Option<Vec<String>> {
+        Some(
+            self.spec
+                .selected_columns(options)
+                .map(|col| col.title.clone())
+                .collect(),
+        )
+    }
+
+    fn data<O: StructTableOptions>(&self, options: &O) -> Vec<Option<String>> {
+        self.spec
+            .selected_columns(options)
+            .map(|col| {
+                self.value
+                    .pointer(&col.pointer)
+                    .filter(|val| !val.is_null())
+                    .map(|val| match (col.format, val) {
+                        (ColumnFormat::Text, Value::String(x)) => x.clone(),
+                        (ColumnFormat::Text, _) => val.to_string(),
+                        (ColumnFormat::Json, _) => if options.pretty_mode() {
+                            serde_json::to_string_pretty(val)
+                        } else {
+                            serde_json::to_string(val)
+                        }
+                        .unwrap_or_else(|err| {
+                            options.serialization_error_placeholder(&col.title, &err)
+                        }),
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::OutputConfig;
+
+    fn spec() -> TableSpec {
+        TableSpec {
+            columns: vec![
+                ColumnSpec {
+                    title: "ID".into(),
+                    pointer: "/id".into(),
+                    ..Default::default()
+                },
+                ColumnSpec {
+                    title: "Name".into(),
+                    pointer: "/name".into(),
+                    ..Default::default()
+                },
+                ColumnSpec {
+                    title: "Meta".into(),
+                    pointer: "/meta".into(),
+                    wide: true,
+                    format: ColumnFormat::Json,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let spec: TableSpec = serde_json::from_value(json!({
+            "columns": [
+                {"title": "ID", "pointer": "/id"},
+                {"title": "Name", "pointer": "/name"},
+                {"title": "Meta", "pointer": "/meta", "wide": true, "format": "json"},
+            ]
+        }))
+        .unwrap();
+        assert_eq!(spec, self::spec());
+    }
+
+    #[test]
+    fn test_build_table() {
+        let data = json!({"id": 1, "name": "foo", "meta": {"a": "b"}});
+        assert_eq!(
+            spec().build_table(
+                &data,
+                &OutputConfig {
+                    wide: true,
+                    ..Default::default()
+                }
+            ),
+            (
+                vec!["Attribute".into(), "Value".into()],
+                vec![
+                    vec!["ID".into(), "1".into()],
+                    vec!["Name".into(), "foo".into()],
+                    vec!["Meta".into(), "{\"a\":\"b\"}".into()],
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_build_list_table() {
+        let data = [
+            json!({"id": 1, "name": "foo", "meta": {"a": "b"}}),
+            json!({"id": 2, "name": null}),
+        ];
+        assert_eq!(
+            spec().build_list_table(data.iter(), &OutputConfig::default()),
+            (
+                vec!["ID".into(), "Name".into()],
+                vec![vec!["1".into(), "foo".into()], vec!["2".into(), " ".into()],]
+            )
+        );
+    }
+}
@@ -0,0 +1,115 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Column statistics of the built tables
+
+use std::collections::BTreeSet;
+
+/// Statistics of the single table column
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColumnStats {
+    /// Column title
+    pub title: String,
+    /// Maximal width (in characters) of the column including the title
+    pub max_width: usize,
+    /// Number of distinct values in the column
+    pub distinct: usize,
+    /// Minimal value of the numeric cells (if any)
+    pub min: Option<f64>,
+    /// Maximal value of the numeric cells (if any)
+    pub max: Option<f64>,
+}
+
+/// Calculate statistics of every column of the list table in a single pass
+///
+/// Multiline cells are measured by their longest line. Cells that can not be parsed as numbers are
+/// ignored for `min` and `max`.
+pub fn column_stats(headers: &[String], rows: &[Vec<String>]) -> Vec<ColumnStats> {
+    let mut distinct: Vec<BTreeSet<&str>> = vec![BTreeSet::new(); headers.len()];
+    let mut stats: Vec<ColumnStats> = headers
+        .iter()
+        .map(|title| ColumnStats {
+            title: title.clone(),
+            max_width: text_width(title),
+            ..Default::default()
+        })
+        .collect();
+    for row in rows {
+        for ((cell, stat), values) in row.iter().zip(stats.iter_mut()).zip(distinct.iter_mut()) {
+            stat.max_width = stat.max_width.max(text_width(cell));
+            values.insert(cell.as_str());
+            if let Ok(num) = cell.trim().parse::<f64>() {
+                stat.min = Some(stat.min.map_or(num, |x| x.min(num)));
+                stat.max = Some(stat.max.map_or(num, |x| x.max(num)));
+            }
+        }
+    }
+    for (stat, values) in stats.iter_mut().zip(distinct) {
+        stat.distinct = values.len();
+    }
+    stats
+}
+
+/// Width of the text in characters (longest line for the multiline text)
+fn text_width(text: &str) -> usize {
+    text.lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_stats() {
+        let headers = vec!["ID".to_string(), "Name".to_string(), "Data".to_string()];
+        let rows = vec![
+            vec![
+                "1".to_string(),
+                "Scooby".to_string(),
+                "{\n  \"a\": 1\n}".to_string(),
+            ],
+            vec!["20".to_string(), "Doo".to_string(), " ".to_string()],
+            vec!["-3.5".to_string(), "Doo".to_string(), " ".to_string()],
+        ];
+        assert_eq!(
+            column_stats(&headers, &rows),
+            vec![
+                ColumnStats {
+                    title: "ID".into(),
+                    max_width: 4,
+                    distinct: 3,
+                    min: Some(-3.5),
+                    max: Some(20.0),
+                },
+                ColumnStats {
+                    title: "Name".into(),
+                    max_width: 6,
+                    distinct: 2,
+                    min: None,
+                    max: None,
+                },
+                ColumnStats {
+                    title: "Data".into(),
+                    max_width: 8,
+                    distinct: 2,
+                    min: None,
+                    max: None,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,306 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Built table with the post-processing combinators
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::{titles_match, TableIssue, ValidationError};
+
+/// Built list table
+///
+/// Wraps the headers and rows as returned by the [`build_list_table`](crate::build_list_table)
+/// and allows chaining of the selection methods, i.e.
+/// `Table::from(build_list_table(data, &options)).select(["ID", "Name"]).sort_by("Name").take(20)`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Table {
+    /// Column headers
+    pub headers: Vec<String>,
+    /// Rows
+    pub rows: Vec<Vec<String>>,
+}
+
+impl From<(Vec<String>, Vec<Vec<String>>)> for Table {
+    fn from((headers, rows): (Vec<String>, Vec<Vec<String>>)) -> Self {
+        Self { headers, rows }
+    }
+}
+
+impl Table {
+    /// Return headers and rows of the table
+    pub fn into_parts(self) -> (Vec<String>, Vec<Vec<String>>) {
+        (self.headers, self.rows)
+    }
+
+    /// Index of the column by its title
+    pub fn column_index(&self, title: &str) -> Option<usize> {
+        self.headers.iter().position(|hdr| titles_match(hdr, title))
+    }
+
+    /// Keep only the given columns in the given order. Unknown columns are ignored.
+    pub fn select<I, S>(self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let indexes: Vec<usize> = columns
+            .into_iter()
+            .filter_map(|col| self.column_index(col.as_ref()))
+            .collect();
+        let headers = indexes
+            .iter()
+            .map(|idx| self.headers[*idx].clone())
+            .collect();
+        let rows = self
+            .rows
+            .into_iter()
+            .map(|row| {
+                indexes
+                    .iter()
+                    .map(|idx| row.get(*idx).cloned().unwrap_or_default())
+                    .collect()
+            })
+            .collect();
+        Self { headers, rows }
+    }
+
+    /// Keep only the rows matching the predicate
+    pub fn filter<F>(mut self, mut predicate: F) -> Self
+    where
+        F: FnMut(&[String]) -> bool,
+    {
+        self.rows.retain(|row| predicate(row));
+        self
+    }
+
+    /// Sort rows by the column (numerically when both values are numbers, numbers go before the
+    /// text). Sorting is stable and the table is returned unchanged when the column is not
+    /// present.
+    pub fn sort_by(mut self, column: &str) -> Self {
+        if let Some(idx) = self.column_index(column) {
+            self.rows
+                .sort_by(|a, b| compare_cells(a.get(idx), b.get(idx)));
+        }
+        self
+    }
+
+    /// Keep only the first `count` rows
+    pub fn take(mut self, count: usize) -> Self {
+        self.rows.truncate(count);
+        self
+    }
+}
+
+impl Table {
+    /// Check that every row has as many cells as there are headers and that headers are unique
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.validate_with::<_, &str, _, &str>([], [])
+    }
+
+    /// Check the table structure (as [`validate`](Self::validate)) as well as presence of the
+    /// expected `columns` and uniqueness of the combined values of the `key_columns`
+    pub fn validate_with<C, S, K, KS>(
+        &self,
+        columns: C,
+        key_columns: K,
+    ) -> Result<(), ValidationError>
+    where
+        C: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        K: IntoIterator<Item = KS>,
+        KS: AsRef<str>,
+    {
+        let mut issues = check_rows(&self.headers, &self.rows);
+        for (idx, hdr) in self.headers.iter().enumerate() {
+            if self.headers[..idx].contains(hdr) {
+                issues.push(TableIssue::DuplicateHeader { title: hdr.clone() });
+            }
+        }
+        for col in columns {
+            if self.column_index(col.as_ref()).is_none() {
+                issues.push(TableIssue::UnknownColumn {
+                    title: col.as_ref().to_string(),
+                });
+            }
+        }
+        let mut key_indexes = Vec::new();
+        for key in key_columns {
+            match self.column_index(key.as_ref()) {
+                Some(idx) => key_indexes.push(idx),
+                None => issues.push(TableIssue::UnknownColumn {
+                    title: key.as_ref().to_string(),
+                }),
+            }
+        }
+        if !key_indexes.is_empty() {
+            let mut keys: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+            for (row_idx, row) in self.rows.iter().enumerate() {
+                let key = key_indexes
+                    .iter()
+                    .map(|idx| row.get(*idx).map(String::as_str).unwrap_or_default())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                keys.entry(key).or_default().push(row_idx);
+            }
+            issues.extend(
+                keys.into_iter()
+                    .filter(|(_, rows)| rows.len() > 1)
+                    .map(|(key, rows)| TableIssue::DuplicateKey { key, rows }),
+            );
+        }
+        match issues.is_empty() {
+            true => Ok(()),
+            false => Err(ValidationError { issues }),
+        }
+    }
+}
+
+/// Check that every row has as many cells as there are headers
+pub(crate) fn check_rows(headers: &[String], rows: &[Vec<String>]) -> Vec<TableIssue> {
+    rows.iter()
+        .enumerate()
+        .filter(|(_, row)| row.len() != headers.len())
+        .map(|(row, cells)| TableIssue::RowLength {
+            row,
+            cells: cells.len(),
+            headers: headers.len(),
+        })
+        .collect()
+}
+
+/// Compare cells numerically when possible and as strings otherwise
+///
+/// Missing cells go first, numbers go before the text to keep the order total.
+fn compare_cells(a: Option<&String>, b: Option<&String>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+            (Ok(x), Ok(y)) => x.total_cmp(&y),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => a.cmp(b),
+        },
+        (a, b) => a.cmp(&b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combinators() {
+        let table = Table::from((
+            vec!["ID".to_string(), "Name".to_string(), "Size".to_string()],
+            vec![
+                vec!["1".to_string(), "b".to_string(), "10".to_string()],
+                vec!["2".to_string(), "a".to_string(), "9".to_string()],
+                vec!["3".to_string(), "c".to_string(), "100".to_string()],
+            ],
+        ));
+        assert_eq!(
+            table
+                .clone()
+                .select(["name", "id", "missing"])
+                .sort_by("Name")
+                .take(2)
+                .into_parts(),
+            (
+                vec!["Name".to_string(), "ID".to_string()],
+                vec![
+                    vec!["a".to_string(), "2".to_string()],
+                    vec!["b".to_string(), "1".to_string()],
+                ]
+            )
+        );
+        assert_eq!(
+            table
+                .filter(|row| row[0] != "1")
+                .sort_by("Size")
+                .select(["Size"])
+                .rows,
+            vec![vec!["9".to_string()], vec!["100".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_sort_mixed() {
+        let column = ["1a", "10", "b", "NaN", "2", "-1", "a", "2.5", "1e1"];
+        let table = Table::from((
+            vec!["Name".to_string()],
+            column.iter().map(|x| vec![x.to_string()]).collect(),
+        ));
+        assert_eq!(
+            table.sort_by("Name").rows,
+            ["-1", "2", "2.5", "10", "1e1", "NaN", "1a", "a", "b"]
+                .iter()
+                .map(|x| vec![x.to_string()])
+                .collect::<Vec<_>>()
+        );
+
+        // Large mixed column sorts without panicking on the inconsistent comparison
+        let mut rows: Vec<Vec<String>> = (0..200)
+            .map(|idx| match idx % 4 {
+                0 => vec![format!("{}", idx * 7 % 13)],
+                1 => vec![format!("{}a", idx % 5)],
+                2 => vec!["NaN".to_string()],
+                _ => vec![format!("x{idx}")],
+            })
+            .collect();
+        rows.push(Vec::new());
+        let sorted = Table::from((vec!["Name".to_string()], rows))
+            .sort_by("Name")
+            .rows;
+        assert!(sorted
+            .windows(2)
+            .all(|w| compare_cells(w[0].first(), w[1].first()) != Ordering::Greater));
+        assert!(sorted[0].is_empty());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut table = Table::from((
+            vec!["ID".to_string(), "Name".to_string()],
+            vec![
+                vec!["1".to_string(), "a".to_string()],
+                vec!["1".to_string(), "b".to_string()],
+            ],
+        ));
+        assert_eq!(table.validate(), Ok(()));
+        assert_eq!(table.validate_with(["name"], ["name"]), Ok(()));
+        table.rows.push(vec!["1".to_string()]);
+        let err = table.validate_with(["Size"], ["ID"]).unwrap_err();
+        assert_eq!(
+            err.issues,
+            vec![
+                TableIssue::RowLength {
+                    row: 2,
+                    cells: 1,
+                    headers: 2
+                },
+                TableIssue::UnknownColumn {
+                    title: "Size".to_string()
+                },
+                TableIssue::DuplicateKey {
+                    key: "1".to_string(),
+                    rows: vec![0, 1, 2]
+                },
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "invalid table: row 2 has 1 cell(s) for 2 header(s); unknown column `Size`; key `1` is not unique (rows [0, 1, 2])"
+        );
+    }
+}
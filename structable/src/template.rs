@@ -0,0 +1,67 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Template driven output of the built tables
+
+use std::collections::BTreeMap;
+
+use minijinja::{context, Environment, Value};
+
+pub use minijinja::Error as TemplateError;
+
+/// Render every row of the list table using the `minijinja` template
+///
+/// The row is available in the template as the `row` map with column titles as keys (i.e.
+/// `{{ row.ID }}: {{ row.Status }}`), while column titles are available as `headers`. Returns one
+/// rendered string per row.
+pub fn render_template(
+    template: &str,
+    headers: &[String],
+    rows: &[Vec<String>],
+) -> Result<Vec<String>, TemplateError> {
+    let mut env = Environment::new();
+    env.add_template("row", template)?;
+    let tmpl = env.get_template("row")?;
+    let headers_value = Value::from(headers.to_vec());
+    rows.iter()
+        .map(|row| {
+            let row: BTreeMap<String, String> =
+                headers.iter().cloned().zip(row.iter().cloned()).collect();
+            tmpl.render(context! { row, headers => headers_value.clone() })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let headers = vec!["ID".to_string(), "Name".to_string()];
+        let rows = vec![
+            vec!["1".to_string(), "Scooby".to_string()],
+            vec!["2".to_string(), "John".to_string()],
+        ];
+        assert_eq!(
+            render_template("{{ row.ID }}: {{ row.Name | upper }}", &headers, &rows).unwrap(),
+            vec!["1: SCOOBY".to_string(), "2: JOHN".to_string()]
+        );
+        assert_eq!(
+            render_template("{{ headers | join(',') }}", &headers, &rows[..1]).unwrap(),
+            vec!["ID,Name".to_string()]
+        );
+        assert!(render_template("{{ row.ID", &headers, &rows).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Helpers for testing of the built tables
+//!
+//! ```rust
+//! # use structable::{assert_list_table_eq, build_list_table, OutputConfig, StructTable, StructTableOptions};
+//! #[derive(StructTable)]
+//! struct User {
+//!     id: u32,
+//! }
+//!
+//! assert_list_table_eq!(
+//!     build_list_table([User { id: 1 }].iter(), &OutputConfig::default()),
+//!     (["id"], [["1"]])
+//! );
+//! ```
+
+/// Describe differences of the list tables
+///
+/// Returns `None` when the tables are equal and the human readable report of missing and
+/// unexpected columns and mismatched cells otherwise.
+pub fn list_table_diff<H, R, C, EH, ER, EC>(left: (H, R), right: (EH, ER)) -> Option<String>
+where
+    H: IntoIterator,
+    H::Item: AsRef<str>,
+    R: IntoIterator<Item = C>,
+    C: IntoIterator,
+    C::Item: AsRef<str>,
+    EH: IntoIterator,
+    EH::Item: AsRef<str>,
+    ER: IntoIterator<Item = EC>,
+    EC: IntoIterator,
+    EC::Item: AsRef<str>,
+{
+    let (left_headers, left_rows) = collect(left.0, left.1);
+    let (right_headers, right_rows) = collect(right.0, right.1);
+    let mut report: Vec<String> = Vec::new();
+    for hdr in right_headers.iter().filter(|x| !left_headers.contains(x)) {
+        report.push(format!("missing column `{hdr}`"));
+    }
+    for hdr in left_headers.iter().filter(|x| !right_headers.contains(x)) {
+        report.push(format!("unexpected column `{hdr}`"));
+    }
+    if report.is_empty() && left_headers != right_headers {
+        report.push(format!(
+            "columns order differs: {left_headers:?} != {right_headers:?}"
+        ));
+    }
+    if left_rows.len() != right_rows.len() {
+        report.push(format!(
+            "number of rows differs: {} != {}",
+            left_rows.len(),
+            right_rows.len()
+        ));
+    }
+    for (idx, (left_row, right_row)) in left_rows.iter().zip(right_rows.iter()).enumerate() {
+        for (col, hdr) in right_headers.iter().enumerate() {
+            let Some(left_col) = left_headers.iter().position(|x| x == hdr) else {
+                continue;
+            };
+            let left_cell = left_row.get(left_col);
+            let right_cell = right_row.get(col);
+            if left_cell != right_cell {
+                report.push(format!(
+                    "row {idx}, column `{hdr}`: {left_cell:?} != {right_cell:?}"
+                ));
+            }
+        }
+    }
+    match report.is_empty() {
+        true => None,
+        false => Some(report.join("\n")),
+    }
+}
+
+/// Collect headers and rows into owned strings
+fn collect<H, R, C>(headers: H, rows: R) -> (Vec<String>, Vec<Vec<String>>)
+where
+    H: IntoIterator,
+    H::Item: AsRef<str>,
+    R: IntoIterator<Item = C>,
+    C: IntoIterator,
+    C::Item: AsRef<str>,
+{
+    (
+        headers
+            .into_iter()
+            .map(|x| x.as_ref().to_string())
+            .collect(),
+        rows.into_iter()
+            .map(|row| row.into_iter().map(|x| x.as_ref().to_string()).collect())
+            .collect(),
+    )
+}
+
+/// Assert that the list table (headers and rows) equals to the expected one
+///
+/// The expected table can be given with any string-like values (i.e. `(["id"], [["1"]])`). On
+/// failure missing and unexpected columns and mismatched cells are reported.
+#[macro_export]
+macro_rules! assert_list_table_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        if let Some(diff) = $crate::testing::list_table_diff($left, $right) {
+            panic!("list tables differ:\n{}", diff);
+        }
+    };
+}
+
+/// Assert that the single structure table (`["Attribute", "Value"]` rows) equals to the expected
+/// one given as the attribute/value pairs (i.e. `[("id", "1"), ("name", "foo")]`)
+#[macro_export]
+macro_rules! assert_table_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        let (_, rows) = $left;
+        let expected: Vec<Vec<String>> = $right
+            .into_iter()
+            .map(|(k, v)| vec![k.to_string(), v.to_string()])
+            .collect();
+        if let Some(diff) = $crate::testing::list_table_diff(
+            (["Attribute", "Value"], rows),
+            (["Attribute", "Value"], expected),
+        ) {
+            panic!("tables differ:\n{}", diff);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_list_table, build_table, OutputConfig, StructTable, StructTableOptions};
+
+    #[derive(StructTable)]
+    struct User {
+        id: u32,
+        name: &'static str,
+    }
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(
+            list_table_diff(
+                (vec!["id", "extra"], vec![vec!["1", "x"], vec!["2", "y"]]),
+                (["id", "name"], [["1", "a"]])
+            ),
+            Some(
+                "missing column `name`\nunexpected column `extra`\nnumber of rows differs: 2 != 1"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            list_table_diff((["id"], [["1"]]), (["id"], [["2"]])),
+            Some("row 0, column `id`: Some(\"1\") != Some(\"2\")".to_string())
+        );
+    }
+
+    #[test]
+    fn test_macros() {
+        let data = [User { id: 1, name: "a" }];
+        let config = OutputConfig::default();
+        assert_list_table_eq!(
+            build_list_table(data.iter(), &config),
+            (["id", "name"], [["1", "a"]])
+        );
+        assert_table_eq!(build_table(&data[0], &config), [("id", "1"), ("name", "a")]);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 0, column `name`")]
+    fn test_macro_failure() {
+        assert_list_table_eq!(
+            build_list_table([User { id: 1, name: "a" }].iter(), &OutputConfig::default()),
+            (["id", "name"], [["1", "b"]])
+        );
+    }
+}
@@ -0,0 +1,106 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// SPDX-License-Identifier: Apache-2.0
+
+//! Serializable views of the structures
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::{l10n, StructTable, StructTableOptions};
+
+/// View of the structure serialized as the map of the selected columns
+///
+/// Only the columns returned with the options are emitted with their (renamed) titles as keys and
+/// rendered (redacted) values (`null` for missing values), so that any serde format outputs
+/// exactly what the table shows. A list of entries can be serialized as `Vec<SelectedView>`.
+pub struct SelectedView<'a, T, O>(pub &'a T, pub &'a O);
+
+impl<T, O> Serialize for SelectedView<'_, T, O>
+where
+    T: StructTable,
+    O: StructTableOptions,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let SelectedView(data, options) = self;
+        let headers = T::class_headers(*options)
+            .or_else(|| data.instance_headers(*options))
+            .unwrap_or_default();
+        let values = data.data(*options);
+        let schema = T::schema();
+        let mut map = serializer.serialize_map(Some(headers.len()))?;
+        for (title, value) in headers.iter().zip(values) {
+            let value = match value {
+                Some(_) if options.redact_field(title) => {
+                    Some(l10n::redacted_placeholder(*options))
+                }
+                value => value,
+            };
+            let title = l10n::output_title(*options, &schema, title);
+            map.serialize_entry(&title, &value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{OutputConfig, REDACTED_PLACEHOLDER};
+
+    #[derive(StructTable)]
+    struct Data {
+        #[structable(title = "ID")]
+        id: u32,
+        #[structable(optional)]
+        name: Option<String>,
+        #[structable(wide)]
+        extra: String,
+    }
+
+    #[test]
+    fn test_selected_view() {
+        let data = [
+            Data {
+                id: 1,
+                name: Some("a".into()),
+                extra: "x".into(),
+            },
+            Data {
+                id: 2,
+                name: None,
+                extra: "y".into(),
+            },
+        ];
+        let config = OutputConfig::default();
+        let views: Vec<SelectedView<Data, OutputConfig>> = data
+            .iter()
+            .map(|item| SelectedView(item, &config))
+            .collect();
+        assert_eq!(
+            serde_json::to_value(&views).unwrap(),
+            json!([{"ID": "1", "name": "a"}, {"ID": "2", "name": null}])
+        );
+
+        let config = OutputConfig {
+            rename: [("id".to_string(), "Key".to_string())].into(),
+            redact_fields: ["name".to_string()].into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(SelectedView(&data[0], &config)).unwrap(),
+            json!({"Key": "1", "name": REDACTED_PLACEHOLDER})
+        );
+    }
+}
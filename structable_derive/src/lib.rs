@@ -19,50 +19,10 @@
 //! as rows and columns).
 //!
 //! ```rust
-//! # use std::collections::BTreeSet;
 //! # use serde_json::Value;
 //! # use serde::Serialize;
-//! # use structable_derive::StructTable;
-//! #
-//! # pub trait StructTable {
-//! #     fn class_headers<O: StructTableOptions>(config: &O) -> Option<Vec<String>>;
-//! #     fn instance_headers<O: StructTableOptions>(&self, config: &O) -> Option<Vec<String>> { None }
-//! #     fn data<O: StructTableOptions>(&self, config: &O) -> Vec<Option<String>>;
-//! #     fn status(&self) -> Option<String>;
-//! # }
-//! #
-//! # #[derive(Clone, Debug, Default)]
-//! # pub struct OutputConfig {
-//! #     pub fields: BTreeSet<String>,
-//! #     pub wide: bool,
-//! #     pub pretty: bool,
-//! # }
-//! #
-//! # pub trait StructTableOptions {
-//! #     fn wide_mode(&self) -> bool;
-//! #     fn pretty_mode(&self) -> bool;
-//! #     fn should_return_field<S: AsRef<str>>(&self, field: S, is_wide_field: bool) -> bool;
-//! #     fn field_data_json_pointer<S: AsRef<str>>(&self, _field: S) -> Option<String> {None}
-//! # }
-//! #
-//! # impl StructTableOptions for OutputConfig {
-//! #     fn wide_mode(&self) -> bool {
-//! #         self.wide
-//! #     }
-//! #
-//! #     fn pretty_mode(&self) -> bool {
-//! #         self.pretty
-//! #     }
-//! #
-//! #     fn should_return_field<S: AsRef<str>>(&self, field: S, is_wide_field: bool) -> bool {
-//! #         if !is_wide_field {
-//! #             self.fields.is_empty() || self.fields.contains(field.as_ref())
-//! #         } else {
-//! #             (self.fields.is_empty() && self.wide_mode()) || self.fields.contains(field.as_ref())
-//! #         }
-//! #     }
-//! # }
-//! #
+//! use structable::{StructTable, StructTableOptions};
+//!
 //! #[derive(Serialize, StructTable)]
 //! struct User {
 //!     #[structable(title = "ID")]
@@ -12,7 +12,7 @@
 //
 // SPDX-License-Identifier: Apache-2.0
 
-use darling::{ast, FromDeriveInput, FromField};
+use darling::{ast, FromDeriveInput, FromField, FromMeta, FromVariant};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 
@@ -20,7 +20,11 @@ use quote::{quote, ToTokens};
 /// composable; each darling-dependent crate should have its own struct to handle
 /// when its trait is derived.
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(structable), supports(struct_any))]
+#[darling(
+    attributes(structable),
+    supports(struct_any, enum_any),
+    and_then = Self::index_fields
+)]
 pub(crate) struct TableStructInputReceiver {
     /// The struct ident.
     ident: syn::Ident,
@@ -29,29 +33,210 @@ pub(crate) struct TableStructInputReceiver {
     /// to work with types that declare generics.
     generics: syn::Generics,
 
-    /// Receives the body of the struct or enum. We don't care about
-    /// struct fields because we previously told darling we only accept structs.
-    data: ast::Data<(), TableStructFieldReceiver>,
+    /// Receives the body of the struct or enum.
+    data: ast::Data<TableEnumVariantReceiver, TableStructFieldReceiver>,
+
+    /// Whether headers depend on the instance content (`instance_headers` instead of
+    /// `class_headers`). Optional fields without value are not returned at all.
+    #[darling(default)]
+    dynamic: bool,
+
+    /// Respect `serde` attributes of the fields (`skip`, `skip_serializing`, `rename`)
+    #[darling(default)]
+    use_serde_attrs: bool,
+
+    /// Implement the trait for the remote type (with the same fields) instead of the annotated
+    /// one, similarly to the `serde(remote)`
+    remote: Option<syn::Path>,
+
+    /// All fields are returned in the wide mode only unless marked as `brief`
+    #[darling(default)]
+    default_wide: bool,
+
+    /// Columns computed by the methods of the structure
+    #[darling(multiple)]
+    compute: Vec<ComputedColumn>,
+}
+
+/// Column computed by the method of the structure
+#[derive(Debug, FromMeta)]
+struct ComputedColumn {
+    /// Column title
+    title: String,
+    /// Method (`fn(&self) -> impl ToString`) producing the value
+    method: syn::Ident,
+    /// Column is returned in the wide mode only
+    #[darling(default)]
+    wide: bool,
+}
+
+/// Text direction of the field values
+#[derive(Debug, Default, FromMeta)]
+enum Direction {
+    #[default]
+    #[darling(rename = "auto")]
+    Auto,
+    #[darling(rename = "ltr")]
+    Ltr,
+    #[darling(rename = "rtl")]
+    Rtl,
+}
+
+/// Position of the ellipsis in the truncated field values
+#[derive(Debug, Default, FromMeta)]
+enum Ellipsis {
+    #[default]
+    #[darling(rename = "end")]
+    End,
+    #[darling(rename = "start")]
+    Start,
+    #[darling(rename = "middle")]
+    Middle,
+}
+
+/// Number of the collection elements with the optional label (i.e. `3 attachments`)
+#[derive(Debug)]
+struct Count {
+    /// Label following the number
+    label: Option<String>,
+}
+
+impl FromMeta for Count {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self { label: None })
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(Self {
+            label: Some(value.to_string()),
+        })
+    }
+}
+
+/// Verbosity level of the `wide` field (plain `wide` is the level `1`)
+#[derive(Debug, Default, Clone, Copy)]
+struct Verbosity(u8);
+
+impl FromMeta for Verbosity {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self(1))
+    }
+
+    fn from_bool(value: bool) -> darling::Result<Self> {
+        Ok(Self(u8::from(value)))
+    }
+
+    fn from_value(value: &syn::Lit) -> darling::Result<Self> {
+        match value {
+            syn::Lit::Int(level) => Ok(Self(level.base10_parse()?)),
+            syn::Lit::Bool(flag) => Self::from_bool(flag.value),
+            _ => Err(darling::Error::unexpected_lit_type(value)),
+        }
+    }
+}
+
+/// Horizontal alignment of the field values
+#[derive(Debug, Default, FromMeta)]
+enum Align {
+    #[default]
+    #[darling(rename = "left")]
+    Left,
+    #[darling(rename = "right")]
+    Right,
+    #[darling(rename = "center")]
+    Center,
+}
+
+/// Mapping of the rendered field value to the label
+#[derive(Debug, FromMeta)]
+struct ValueMap {
+    /// Rendered value
+    value: String,
+    /// Label to show instead of the value
+    to: String,
+}
+
+/// Labels of the boolean values given as `<true label>/<false label>`
+#[derive(Debug)]
+struct BoolLabels {
+    /// Label of the `true`
+    yes: String,
+    /// Label of the `false`
+    no: String,
+}
+
+impl FromMeta for BoolLabels {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value.split_once('/') {
+            Some((yes, no)) if !no.contains('/') => Ok(Self {
+                yes: yes.to_string(),
+                no: no.to_string(),
+            }),
+            _ => Err(darling::Error::custom(
+                "expected `<true label>/<false label>` (i.e. `yes/no`)",
+            )),
+        }
+    }
 }
 
 #[derive(Debug, FromField)]
-#[darling(attributes(structable))]
+#[darling(attributes(structable), forward_attrs(serde, doc))]
 struct TableStructFieldReceiver {
     /// Get the ident of the field. For fields in tuple or newtype structs or
     /// enum bodies, this can be `None`.
     ident: Option<syn::Ident>,
 
+    /// Type of the field
+    ty: syn::Type,
+
+    /// Forwarded `serde` and `doc` attributes of the field.
+    attrs: Vec<syn::Attribute>,
+
     /// Optional alternative title for the field
     title: Option<String>,
 
-    /// Whether option is returned in wide mode only
+    /// Field is never returned
     #[darling(default)]
-    wide: bool,
+    skip: bool,
+
+    /// Merge columns of the nested `StructTable` into the parent row
+    #[darling(default)]
+    flatten: bool,
+
+    /// Comma separated groups the field belongs to
+    tags: Option<String>,
+
+    /// Structure is rendered entirely by the `StructTable` of this field
+    #[darling(default)]
+    inner: bool,
+
+    /// Keys of the map become additional columns of the instance
+    #[darling(default)]
+    spread: bool,
+
+    /// Whether option is returned in wide mode only (optionally with the verbosity level)
+    #[darling(default)]
+    wide: Verbosity,
+
+    /// Whether option is returned in the normal mode when the structure is `default_wide`
+    #[darling(default)]
+    brief: bool,
 
     /// Whether option is returned is optional or not
     #[darling(default)]
     optional: bool,
 
+    /// Treat the value equal to the `Default::default()` as missing
+    #[darling(default)]
+    optional_default: bool,
+
+    /// Treat the empty value (string, collection or json object) as missing
+    #[darling(default)]
+    skip_if_empty: bool,
+
+    /// Placeholder shown instead of the missing value
+    default_value: Option<String>,
+
     /// Apply `to_string_pretty` instead of `to_string` for the value
     #[darling(default)]
     pretty: bool,
@@ -60,182 +245,1459 @@ struct TableStructFieldReceiver {
     #[darling(default)]
     serialize: bool,
 
+    /// Render keys of the nested object as separate `parent.child` rows of the single table
+    #[darling(default)]
+    expand: bool,
+
     /// Whether this is a `status` field
     #[darling(default)]
     status: bool,
+
+    /// Omit column from the list table when it is empty in every row
+    #[darling(default)]
+    hide_if_empty: bool,
+
+    /// Method of the structure to get the field value from instead of reading the field
+    getter: Option<syn::Ident>,
+
+    /// Function (`fn(&T) -> String`) rendering the field value instead of the `to_string`
+    format_with: Option<syn::Path>,
+
+    /// Function with the `serde` `serialize_with` signature producing the json of the field
+    serialize_with: Option<syn::Path>,
+
+    /// Format of the timestamp field
+    datetime_format: Option<String>,
+
+    /// Separator of the joined collection elements
+    join: Option<String>,
+
+    /// Map rendered values to labels
+    #[darling(multiple)]
+    map: Vec<ValueMap>,
+
+    /// Labels of the boolean values
+    bool_labels: Option<BoolLabels>,
+
+    /// Render the number of elements of the collection outside of the wide mode
+    count: Option<Count>,
+
+    /// Convert the value to the upper case
+    #[darling(default)]
+    uppercase: bool,
+
+    /// Convert the value to the lower case
+    #[darling(default)]
+    lowercase: bool,
+
+    /// Capitalize words of the value
+    #[darling(default)]
+    titlecase: bool,
+
+    /// Insert separator between digit groups of the integer value
+    #[darling(default)]
+    thousands: bool,
+
+    /// Unit appended to the value (i.e. `MB`)
+    unit: Option<String>,
+
+    /// Maximum length of the value outside of the wide and pretty modes
+    max_length: Option<usize>,
+
+    /// Render the integer byte count with the binary unit
+    #[darling(default)]
+    bytes: bool,
+
+    /// Render the duration (or integer number of seconds) as `2h 15m`
+    #[darling(default)]
+    duration: bool,
+
+    /// Column priority (lower is more important)
+    #[darling(default)]
+    priority: u32,
+
+    /// Position of the column in the output (instead of the declaration order)
+    order: Option<i32>,
+
+    /// Field is (part of) the row identity
+    #[darling(default)]
+    key: bool,
+
+    /// Example value of the field
+    example: Option<String>,
+
+    /// Text direction hint of the field values
+    #[darling(default)]
+    direction: Direction,
+
+    /// Position of the ellipsis in the truncated field values
+    #[darling(default)]
+    ellipsis: Ellipsis,
+
+    /// Horizontal alignment of the field values
+    #[darling(default)]
+    align: Align,
+
+    /// Style hint of the field values (i.e. `bold`)
+    style: Option<String>,
+
+    /// Color hint of the field values (i.e. `red`)
+    color: Option<String>,
+
+    /// Template of the hyperlink target referencing other fields (i.e. `https://host/{id}`)
+    link: Option<String>,
+
+    /// Key of the localized field title
+    l10n_key: Option<String>,
+
+    /// Local binding of the field of the enum variant
+    #[darling(skip)]
+    binding: Option<syn::Ident>,
+
+    /// Position of the field in the tuple structure (or tuple variant)
+    #[darling(skip)]
+    index: Option<usize>,
 }
 
-impl ToTokens for TableStructInputReceiver {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let TableStructInputReceiver {
-            ref ident,
-            ref generics,
-            ref data,
-        } = *self;
+/// Variant of the enum
+#[derive(Debug, FromVariant)]
+#[darling(attributes(structable), and_then = Self::bind_fields)]
+struct TableEnumVariantReceiver {
+    /// The variant ident.
+    ident: syn::Ident,
 
-        let (imp, ty, wher) = generics.split_for_impl();
-        let fields = data
-            .as_ref()
-            .take_struct()
-            .expect("Should never be enum")
-            .fields;
-
-        let mut vec_struct_headers = Vec::new();
-        let mut vec_struct_fields = Vec::new();
-        let mut status_field: Option<&TableStructFieldReceiver> = None;
-        let mut status_alt_field: Option<&TableStructFieldReceiver> = None;
-
-        for field in fields.iter().filter(|f| f.ident.is_some()) {
-            if let Some(field_ident) = field.ident.as_ref() {
-                //let field_ident = field.ident.as_ref().;
-                let field_title = field.title.clone().unwrap_or(field_ident.to_string());
-                let field_wide = field.wide;
-
-                // Determine how to get the data based in `optional` and `pretty` for list row column
-                let field_vec_value = match field.optional {
-                    false => match field.serialize || field.pretty {
-                        false => quote!(
-                            Some(self. #field_ident .to_string())
-                        ),
-                        true => quote!(
-                            Some(
-                                serde_json::to_value(self. #field_ident.clone())
-                                    .map(|v| {
-                                        options
-                                            .field_data_json_pointer(#field_title)
-                                            .map_or(
-                                                v.to_owned(),
-                                                |jp| {v.pointer(jp.as_ref()).unwrap_or(&serde_json::Value::Null).to_owned()}
-                                            )
-                                    })
-                                    .and_then(|v| {
-                                        if options.pretty_mode() {
-                                            serde_json::to_string_pretty(&v)
-                                        } else {
-                                            serde_json::to_string(&v)
-                                        }
-                                    })
-                                .map(|x| x.trim_matches('"').to_string())
-                                .unwrap_or_else(|_| String::from("<ERROR SERIALIZING DATA>"))
-                            )
-                        ),
-                    },
-                    true => match field.serialize || field.pretty {
-                        false => quote!(
-                            self. #field_ident .clone().map(|x| x.to_string())
-                        ),
-                        true => quote!(
-                            self. #field_ident
-                                .clone()
-                                .map(|x| {
-                                    serde_json::to_value(x)
-                                        .map(|v| {
-                                            options
-                                                .field_data_json_pointer(#field_title)
-                                                .map_or(
-                                                    v.to_owned(),
-                                                    |jp| {v.pointer(jp.as_ref()).unwrap_or(&serde_json::Value::Null).to_owned()}
-                                                )
-                                        })
-                                        .and_then(|v| {
-                                            if options.pretty_mode() {
-                                                serde_json::to_string_pretty(&v)
-                                            } else {
-                                                serde_json::to_string(&v)
-                                            }
-                                        })
-                                    .map(|x| x.trim_matches('"').to_string())
-                                    .unwrap_or_else(|_| String::from("<ERROR SERIALIZING DATA>"))
+    /// Fields of the variant
+    fields: ast::Fields<TableStructFieldReceiver>,
+}
 
-                                })
-                        ),
-                    },
-                };
+impl TableEnumVariantReceiver {
+    /// Access the fields through the local bindings of the `match` arm
+    fn bind_fields(mut self) -> darling::Result<Self> {
+        for (idx, field) in self.fields.fields.iter_mut().enumerate() {
+            field.binding = Some(match &field.ident {
+                Some(ident) => quote::format_ident!(
+                    "__structable_{}",
+                    ident.to_string().trim_start_matches("r#")
+                ),
+                None => {
+                    field.index = Some(idx);
+                    quote::format_ident!("__structable_{}", idx)
+                }
+            });
+        }
+        Ok(self)
+    }
+}
 
-                // Build field values processing for Vec<T> impl
-                let vec_struct_row = quote!(
-                    if options.should_return_field(#field_title, #field_wide) {
-                        row.push(#field_vec_value);
-                    }
-                );
-                // Build field headers processing for the Vec<T> impl
-                let vec_struct_header_row = quote!(
-                    if options.should_return_field(#field_title, #field_wide) {
-                       headers.push(#field_title .to_string());
-                    }
-                );
+/// Collection shapes of the field type rendered by joining the elements
+#[derive(Debug, PartialEq, Eq)]
+enum Collection {
+    /// `Vec<T>`
+    Vec,
+    /// `Vec<Option<T>>`
+    VecOfOption,
+    /// `Option<Vec<T>>`
+    OptionOfVec,
+}
+
+/// Return the single generic type argument of the type with the given name (i.e. `T` of `Vec<T>`)
+fn generic_argument<'a>(ty: &'a syn::Type, name: &str) -> Option<&'a syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first() {
+        Some(syn::GenericArgument::Type(inner)) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+impl TableStructFieldReceiver {
+    /// Collection shape of the field type
+    fn collection(&self) -> Option<Collection> {
+        if let Some(inner) = generic_argument(&self.ty, "Vec") {
+            return match generic_argument(inner, "Option") {
+                Some(_) => Some(Collection::VecOfOption),
+                None => Some(Collection::Vec),
+            };
+        }
+        generic_argument(&self.ty, "Option")
+            .and_then(|inner| generic_argument(inner, "Vec"))
+            .map(|_| Collection::OptionOfVec)
+    }
+
+    /// Name of the field (position for the tuple structures)
+    fn name(&self) -> Option<String> {
+        match (&self.ident, self.index) {
+            (Some(ident), _) => Some(ident.to_string()),
+            (None, Some(idx)) => Some(idx.to_string()),
+            (None, None) => None,
+        }
+    }
+
+    /// Text of the doc comments of the field
+    fn description(&self) -> Option<String> {
+        let lines: Vec<String> = self
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .filter_map(|attr| match &attr.meta {
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    value:
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(doc),
+                            ..
+                        }),
+                    ..
+                }) => Some(doc.value().trim().to_string()),
+                _ => None,
+            })
+            .collect();
+        let description = lines.join("\n").trim().to_string();
+        (!description.is_empty()).then_some(description)
+    }
+
+    /// Groups from the comma separated `tags`
+    fn tags(&self) -> Vec<String> {
+        self.tags
+            .iter()
+            .flat_map(|tags| tags.split(','))
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    /// Expression to access the field value
+    fn accessor(&self) -> TokenStream {
+        match (&self.getter, &self.binding, &self.ident, self.index) {
+            (Some(getter), _, _, _) => quote!(self. #getter ()),
+            (None, Some(binding), _, _) => quote!((*#binding)),
+            (None, None, Some(ident), _) => quote!(self. #ident),
+            (None, None, None, Some(idx)) => {
+                let idx = syn::Index::from(idx);
+                quote!(self. #idx)
+            }
+            (None, None, None, None) => quote!(),
+        }
+    }
 
-                vec_struct_fields.push(vec_struct_row);
-                vec_struct_headers.push(vec_struct_header_row);
+    /// Expression serializing the field value into the `serde_json::Value`
+    ///
+    /// For optional fields the result is wrapped into the `Option`.
+    fn json_value(&self) -> TokenStream {
+        let field_access = self.accessor();
+        match (self.optional, &self.serialize_with) {
+            (false, None) => quote!(serde_json::to_value(&#field_access)),
+            (true, None) => quote!(#field_access .as_ref().map(serde_json::to_value)),
+            (false, Some(path)) => quote!(#path(&#field_access, serde_json::value::Serializer)),
+            (true, Some(path)) => quote!(
+                #field_access .as_ref().map(|v| #path(v, serde_json::value::Serializer))
+            ),
+        }
+    }
 
-                // Save the status or status_alt (the one with name `status`) field
-                if field.status {
-                    status_field = Some(field);
+    /// Whether the field is skipped from serialization by `serde`
+    fn serde_skipped(&self) -> bool {
+        let mut skipped = false;
+        for attr in self
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("serde"))
+        {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                    skipped = true;
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.parse_nested_meta(|nested| {
+                        if nested.input.peek(syn::Token![=]) {
+                            nested.value()?.parse::<syn::Expr>()?;
+                        }
+                        Ok(())
+                    })?;
                 }
-                if field_title.to_lowercase() == "status" {
-                    status_alt_field = Some(field);
+                Ok(())
+            });
+        }
+        skipped
+    }
+
+    /// Name of the field set with `#[serde(rename = "..")]` (or `rename(serialize = "..")`)
+    fn serde_rename(&self) -> Option<String> {
+        let mut rename = None;
+        for attr in self
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("serde"))
+        {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") && meta.input.peek(syn::Token![=]) {
+                    rename = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    let is_rename = meta.path.is_ident("rename");
+                    meta.parse_nested_meta(|nested| {
+                        if nested.input.peek(syn::Token![=]) {
+                            let value = nested.value()?.parse::<syn::Expr>()?;
+                            if let syn::Expr::Lit(syn::ExprLit {
+                                lit: syn::Lit::Str(name),
+                                ..
+                            }) = value
+                            {
+                                if is_rename && nested.path.is_ident("serialize") {
+                                    rename = Some(name.value());
+                                }
+                            }
+                        }
+                        Ok(())
+                    })?;
                 }
+                Ok(())
+            });
+        }
+        rename
+    }
+}
+
+/// Code generated for the fields of the structure (or of the enum struct variant)
+struct FieldsTokens<'a> {
+    /// Code pushing the headers
+    headers: Vec<TokenStream>,
+    /// Code pushing the `FieldInfo`
+    schema: Vec<TokenStream>,
+    /// Field with the code pushing its value into the row (and the same reusing the serialized
+    /// status)
+    rows: Vec<(&'a TableStructFieldReceiver, TokenStream, TokenStream)>,
+    /// Names of the `COL_<FIELD>` constants
+    columns: Vec<syn::Ident>,
+    /// Field providing the (primary) status
+    status: Option<&'a TableStructFieldReceiver>,
+    /// Code pushing the titled values of all status fields
+    statuses: Vec<TokenStream>,
+    /// Code inserting the hyperlink targets
+    links: Vec<TokenStream>,
+}
+
+/// Link template with the `{field}` placeholders replaced by the synthesized argument names
+/// together with the names of the referenced fields (in the order of the arguments)
+///
+/// Fields are referenced by their names (`{id}`, `{type}` for `r#type`) or positions in the
+/// tuple structures (`{0}`).
+fn link_template(template: &str) -> (String, Vec<String>) {
+    let mut res = String::with_capacity(template.len());
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        res.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        // Escaped brace
+        if let Some(escaped) = rest.strip_prefix('{') {
+            res.push_str("{{");
+            rest = escaped;
+            continue;
+        }
+        let Some(end) = rest.find('}') else {
+            res.push('{');
+            break;
+        };
+        let (name, spec) = match rest[..end].split_once(':') {
+            Some((name, spec)) => (name.trim(), Some(spec)),
+            None => (rest[..end].trim(), None),
+        };
+        let name = name.trim_start_matches("r#");
+        let idx = match names.iter().position(|x| x == name) {
+            Some(idx) => idx,
+            None => {
+                names.push(name.to_string());
+                names.len() - 1
             }
+        };
+        res.push_str(&format!("{{__structable_link_{idx}"));
+        if let Some(spec) = spec {
+            res.push(':');
+            res.push_str(spec);
         }
+        res.push('}');
+        rest = &rest[end + 1..];
+    }
+    res.push_str(rest);
+    (res, names)
+}
 
-        // Set status_field to status_alt if no explicit `status` set
-        if status_alt_field.is_some() && status_field.is_none() {
-            status_field = status_alt_field;
+/// Expression rendering the link `template` of the `field` with values of the other `fields`
+fn link_tokens(
+    field: &TableStructFieldReceiver,
+    template: &str,
+    fields: &[&TableStructFieldReceiver],
+) -> TokenStream {
+    let (template, names) = link_template(template);
+    let mut args = Vec::new();
+    for (idx, name) in names.iter().enumerate() {
+        match fields.iter().find(|f| {
+            f.name()
+                .is_some_and(|x| x.trim_start_matches("r#") == name.as_str())
+        }) {
+            Some(other) => {
+                let arg = quote::format_ident!("__structable_link_{}", idx);
+                let other_access = other.accessor();
+                args.push(quote!(#arg = #other_access));
+            }
+            None => {
+                return syn::Error::new_spanned(
+                    &field.ty,
+                    format!("link references unknown field `{name}`"),
+                )
+                .to_compile_error();
+            }
         }
+    }
+    quote!(format!(#template, #(#args),*))
+}
 
-        // Construct code for the `status` trait method for single struct and vec
-        let struct_status = match status_field {
-            Some(field) => {
-                let field_ident = field.ident.as_ref().unwrap();
+/// Generate the code for the named fields
+fn fields_tokens<'a>(
+    fields: impl Iterator<Item = &'a TableStructFieldReceiver>,
+    dynamic: bool,
+    use_serde_attrs: bool,
+    default_wide: bool,
+) -> FieldsTokens<'a> {
+    let mut vec_struct_headers = Vec::new();
+    let mut vec_struct_schema = Vec::new();
+    let mut vec_struct_fields = Vec::new();
+    let mut vec_struct_columns = Vec::new();
+    let mut status_fields: Vec<(&TableStructFieldReceiver, String)> = Vec::new();
+    let mut status_alt_field: Option<(&TableStructFieldReceiver, String)> = None;
+    // Column indexes are not static after the flattened field
+    let mut flattened = false;
 
-                match (field.optional, field.serialize) {
-                    (true, false) => quote!(
-                        self. #field_ident .clone().map(|val| val.to_string())
+    let all_fields: Vec<&TableStructFieldReceiver> = fields.collect();
+    let mut links = Vec::new();
+    let mut fields: Vec<&TableStructFieldReceiver> = all_fields
+        .iter()
+        .copied()
+        .filter(|f| f.name().is_some())
+        .filter(|f| !f.skip)
+        .filter(|f| !(use_serde_attrs && f.serde_skipped()))
+        .collect();
+    // Explicitly ordered fields go first, the rest keeps the declaration order
+    fields.sort_by_key(|f| (f.order.is_none(), f.order));
+
+    for field in fields {
+        if let Some(field_name) = field.name() {
+            if field.flatten {
+                flattened = true;
+                let field_access = field.accessor();
+                let nested_ty = match field.optional {
+                    true => generic_argument(&field.ty, "Option").unwrap_or(&field.ty),
+                    false => &field.ty,
+                };
+                let nested_headers = match (dynamic, field.optional) {
+                    (false, _) => quote!(
+                        <#nested_ty as ::structable::StructTable>::class_headers(options)
                     ),
-                    (false, false) => quote!(
-                        Some(self. #field_ident .to_string())
+                    (true, false) => quote!(
+                        <#nested_ty as ::structable::StructTable>::class_headers(options)
+                            .or_else(|| ::structable::StructTable::instance_headers(&#field_access, options))
                     ),
                     (true, true) => quote!(
-                        self. #field_ident .clone().map(|val| serde_json::to_string(&val).map(|x| x.trim_matches('"').to_string()).unwrap_or_else(|_| String::from("<ERROR SERIALIZING>")))
+                        <#nested_ty as ::structable::StructTable>::class_headers(options)
+                            .or_else(|| #field_access .as_ref().and_then(|v| ::structable::StructTable::instance_headers(v, options)))
+                    ),
+                };
+                let vec_struct_row = match field.optional {
+                    false => quote!(
+                        row.extend(::structable::StructTable::data(&#field_access, options));
+                    ),
+                    true => quote!(
+                        match #field_access .as_ref() {
+                            Some(v) => row.extend(::structable::StructTable::data(v, options)),
+                            None => row.extend(
+                                ::std::iter::repeat(None).take(
+                                    <#nested_ty as ::structable::StructTable>::class_headers(options)
+                                        .map(|h| h.len())
+                                        .unwrap_or_default()
+                                )
+                            ),
+                        }
+                    ),
+                };
+                vec_struct_fields.push((field, vec_struct_row.clone(), vec_struct_row));
+                vec_struct_headers.push(quote!(
+                    headers.extend(#nested_headers .unwrap_or_default());
+                ));
+                vec_struct_schema.push(quote!(
+                    schema.extend(<#nested_ty as ::structable::StructTable>::schema());
+                ));
+                continue;
+            }
+            if field.spread {
+                flattened = true;
+                let field_access = field.accessor();
+                let field_level = match (field.wide, default_wide && !field.brief) {
+                    (Verbosity(0), true) => 1,
+                    (Verbosity(level), _) => level,
+                };
+                let field_tags = field.tags();
+                let condition = quote!(options.should_return_tagged_column(
+                    &k, #field_name, #field_level, &[#(#field_tags),*]
+                ));
+                let entries = quote!(
+                    let mut entries: Vec<(String, _)> = #field_access
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v))
+                        .collect();
+                    entries.sort_by(|a, b| a.0.cmp(&b.0));
+                );
+                let value = match field.serialize {
+                    false => quote!(v.to_string()),
+                    true => quote!(serde_json::to_string(v)
+                        .map(|x| x.trim_matches('"').to_string())
+                        .unwrap_or_else(|err| options.serialization_error_placeholder(&k, &err))),
+                };
+                let vec_struct_row = quote!(
+                    #entries
+                    for (k, v) in entries {
+                        if #condition {
+                            row.push(Some(#value));
+                        }
+                    }
+                );
+                vec_struct_fields.push((field, vec_struct_row.clone(), vec_struct_row));
+                vec_struct_headers.push(quote!(
+                    #entries
+                    for (k, _) in entries {
+                        if #condition {
+                            headers.push(k);
+                        }
+                    }
+                ));
+                continue;
+            }
+            if !flattened {
+                vec_struct_columns.push(quote::format_ident!(
+                    "COL_{}",
+                    field_name.trim_start_matches("r#").to_uppercase()
+                ));
+            }
+            let field_title = field
+                .title
+                .clone()
+                .or_else(|| use_serde_attrs.then(|| field.serde_rename()).flatten())
+                .unwrap_or(field_name.clone());
+            let field_access = field.accessor();
+            let field_level = match (field.wide, default_wide && !field.brief) {
+                (Verbosity(0), true) => 1,
+                (Verbosity(level), _) => level,
+            };
+            let field_wide = field_level > 0;
+
+            // Determine how to get the data based in `optional` and `pretty` for list row column
+            let serialized = |json: TokenStream| match field.optional {
+                false => quote!(
+                    Some(
+                        #json
+                            .map(|v| ::structable::extract_field_data(options, #field_title, v))
+                            .and_then(|v| {
+                                if options.pretty_mode() {
+                                    serde_json::to_string_pretty(&v)
+                                } else {
+                                    serde_json::to_string(&v)
+                                }
+                            })
+                        .map(|x| x.trim_matches('"').to_string())
+                        .unwrap_or_else(|err| options.serialization_error_placeholder(#field_title, &err))
+                    )
+                ),
+                true => quote!(
+                    #json
+                        .map(|x| {
+                            x
+                                .map(|v| ::structable::extract_field_data(options, #field_title, v))
+                                .and_then(|v| {
+                                    if options.pretty_mode() {
+                                        serde_json::to_string_pretty(&v)
+                                    } else {
+                                        serde_json::to_string(&v)
+                                    }
+                                })
+                            .map(|x| x.trim_matches('"').to_string())
+                            .unwrap_or_else(|err| options.serialization_error_placeholder(#field_title, &err))
+                        })
+                ),
+            };
+            let separator = field.join.as_deref().unwrap_or(", ");
+            let join = quote!(
+                .map(|x| x.to_string()).collect::<Vec<String>>().join(#separator)
+            );
+            // Explicit `join` takes precedence over the serialization
+            let serialize =
+                (field.serialize || field.pretty || field.expand || field.serialize_with.is_some())
+                    && field.join.is_none();
+            let field_vec_value = match (field.optional, serialize) {
+                _ if field.format_with.is_some() => {
+                    let format_with = &field.format_with;
+                    match field.optional {
+                        false => quote!(Some(#format_with (&#field_access))),
+                        true => quote!(#field_access .as_ref().map(#format_with)),
+                    }
+                }
+                _ if field.datetime_format.is_some() => {
+                    let format = &field.datetime_format;
+                    match field.optional {
+                        false => quote!(Some(::structable::FormatDateTime::format_datetime(
+                            &#field_access, #format, options.display_timezone()
+                        ))),
+                        true => quote!(#field_access .as_ref().map(|v| {
+                            ::structable::FormatDateTime::format_datetime(v, #format, options.display_timezone())
+                        })),
+                    }
+                }
+                _ if field.duration => match field.optional {
+                    false => quote!(Some(::structable::format_duration(
+                        &#field_access, options.humanize_values()
+                    ))),
+                    true => quote!(#field_access .as_ref().map(|v| {
+                        ::structable::format_duration(v, options.humanize_values())
+                    })),
+                },
+                (_, true) => serialized(field.json_value()),
+                (optional, false) => match (field.collection(), optional) {
+                    (Some(Collection::Vec), _) => quote!(
+                        Some(#field_access .iter() #join)
+                    ),
+                    (Some(Collection::VecOfOption), _) => quote!(
+                        Some(#field_access .iter().flatten() #join)
+                    ),
+                    (Some(Collection::OptionOfVec), _) => quote!(
+                        #field_access .as_ref().map(|v| v.iter() #join)
+                    ),
+                    // Any other iterable collection with the explicit `join`
+                    (None, false) if field.join.is_some() => quote!(
+                        Some((&#field_access).into_iter() #join)
                     ),
-                    (false, true) => quote!(
-                        Some(serde_json::to_string(&self. #field_ident).map(|x| x.trim_matches('"').to_string()).unwrap_or_else(|_| String::from("<ERROR SERIALIZING>")))
+                    (None, true) if field.join.is_some() => quote!(
+                        #field_access .as_ref().map(|v| v.into_iter() #join)
                     ),
+                    (None, false) => quote!(
+                        Some(#field_access .to_string())
+                    ),
+                    (None, true) => quote!(
+                        #field_access .as_ref().map(|x| x.to_string())
+                    ),
+                },
+            };
+            // Same as above, but reusing the value already serialized for the status
+            let field_vec_value_cached = match field.serialize
+                && field.join.is_none()
+                && field.format_with.is_none()
+                && field.datetime_format.is_none()
+                && !field.duration
+            {
+                true => serialized(quote!(__structable_status_json)),
+                false => field_vec_value.clone(),
+            };
+
+            // Number of elements unless the full content is requested with the wide mode
+            let (field_vec_value, field_vec_value_cached) = match &field.count {
+                None => (field_vec_value, field_vec_value_cached),
+                Some(Count { label }) => {
+                    let count = |len: TokenStream| match label {
+                        Some(label) => quote!(format!("{} {}", #len, #label)),
+                        None => quote!(#len .to_string()),
+                    };
+                    let count_value = match field.optional {
+                        false => {
+                            let count = count(quote!(#field_access .len()));
+                            quote!(Some(#count))
+                        }
+                        true => {
+                            let count = count(quote!(v.len()));
+                            quote!(#field_access .as_ref().map(|v| #count))
+                        }
+                    };
+                    (
+                        quote!(if options.wide_mode() { #field_vec_value } else { #count_value }),
+                        quote!(if options.wide_mode() { #field_vec_value_cached } else { #count_value }),
+                    )
+                }
+            };
+
+            // Post-process the rendered value
+            let mut value_transforms: Vec<TokenStream> = Vec::new();
+            if field.skip_if_empty {
+                // Empty collections are only recognized in the serialized values, the plain
+                // strings are taken as they are
+                value_transforms.push(match serialize {
+                    true => quote!(.filter(|v: &String| !matches!(v.as_str(), "" | "[]" | "{}"))),
+                    false => quote!(.filter(|v: &String| !v.is_empty())),
+                });
+            }
+            if !field.map.is_empty() {
+                let map_values = field.map.iter().map(|m| &m.value);
+                let map_labels = field.map.iter().map(|m| &m.to);
+                value_transforms.push(quote!(
+                    .map(|v: String| {
+                        let label: Option<&str> = match v.as_str() {
+                            #(#map_values => Some(#map_labels),)*
+                            _ => None,
+                        };
+                        label.map(String::from).unwrap_or(v)
+                    })
+                ));
+            }
+            if let Some(BoolLabels { yes, no }) = &field.bool_labels {
+                value_transforms.push(quote!(
+                    .map(|v: String| match v.as_str() {
+                        "true" => String::from(#yes),
+                        "false" => String::from(#no),
+                        _ => v,
+                    })
+                ));
+            }
+            // Case is changed after the labels are resolved against the raw values
+            if field.uppercase {
+                value_transforms.push(quote!(.map(|v: String| v.to_uppercase())));
+            }
+            if field.lowercase {
+                value_transforms.push(quote!(.map(|v: String| v.to_lowercase())));
+            }
+            if field.titlecase {
+                value_transforms.push(quote!(.map(::structable::titlecase)));
+            }
+            if field.bytes {
+                value_transforms.push(quote!(
+                    .map(|v: String| match options.humanize_values() {
+                        true => ::structable::humanize_bytes(v),
+                        false => v,
+                    })
+                ));
+            }
+            if field.thousands {
+                value_transforms.push(quote!(
+                    .map(|v: String| ::structable::group_digits(v, options.thousands_separator()))
+                ));
+            }
+            if let Some(unit) = &field.unit {
+                value_transforms.push(quote!(
+                    .map(|v: String| match options.humanize_values() {
+                        true => format!("{} {}", v, #unit),
+                        false => v,
+                    })
+                ));
+            }
+            // Values are wrapped into the hyperlinks by the builders after the truncation
+            if let Some(template) = &field.link {
+                let link = link_tokens(field, template, &all_fields);
+                links.push(quote!(
+                    links.insert(#field_title .to_string(), #link);
+                ));
+            }
+            let field_vec_value = quote!(#field_vec_value #(#value_transforms)*);
+            let field_vec_value_cached = quote!(#field_vec_value_cached #(#value_transforms)*);
+            let (field_vec_value, field_vec_value_cached) = match field.optional_default {
+                false => (field_vec_value, field_vec_value_cached),
+                true => (
+                    quote!(if ::structable::is_default(&#field_access) { None } else { #field_vec_value }),
+                    quote!(if ::structable::is_default(&#field_access) { None } else { #field_vec_value_cached }),
+                ),
+            };
+            let (field_vec_value, field_vec_value_cached) = match &field.default_value {
+                None => (field_vec_value, field_vec_value_cached),
+                Some(default) => (
+                    quote!((#field_vec_value).or_else(|| Some(String::from(#default)))),
+                    quote!((#field_vec_value_cached).or_else(|| Some(String::from(#default)))),
+                ),
+            };
+
+            // In the dynamic mode optional fields without value are not returned at all
+            let field_tags = field.tags();
+            let mut field_condition = quote!(options.should_return_tagged_column(
+                #field_title, #field_name, #field_level, &[#(#field_tags),*]
+            ));
+            if dynamic && field.optional && field.default_value.is_none() {
+                field_condition.extend(quote!(&& #field_access .is_some()));
+            }
+            if dynamic && field.optional_default && field.default_value.is_none() {
+                field_condition.extend(quote!(&& !::structable::is_default(&#field_access)));
+            }
+
+            // Build field values processing for Vec<T> impl
+            let vec_struct_row = quote!(
+                if #field_condition {
+                    row.push(#field_vec_value);
+                }
+            );
+            let vec_struct_row_cached = quote!(
+                if #field_condition {
+                    row.push(#field_vec_value_cached);
+                }
+            );
+            // Build field headers processing for the Vec<T> impl
+            let vec_struct_header_row = quote!(
+                if #field_condition {
+                   headers.push(#field_title .to_string());
                 }
+            );
+
+            vec_struct_fields.push((field, vec_struct_row, vec_struct_row_cached));
+            vec_struct_headers.push(vec_struct_header_row);
+
+            // Build static field information
+            let field_hide_if_empty = field.hide_if_empty;
+            let field_expand = field.expand;
+            let field_priority = field.priority;
+            let field_key = field.key;
+            let field_direction = match field.direction {
+                Direction::Auto => quote!(::structable::TextDirection::Auto),
+                Direction::Ltr => quote!(::structable::TextDirection::Ltr),
+                Direction::Rtl => quote!(::structable::TextDirection::Rtl),
+            };
+            let field_ellipsis = match field.ellipsis {
+                Ellipsis::End => quote!(::structable::EllipsisPosition::End),
+                Ellipsis::Start => quote!(::structable::EllipsisPosition::Start),
+                Ellipsis::Middle => quote!(::structable::EllipsisPosition::Middle),
+            };
+            let field_align = match field.align {
+                Align::Left => quote!(::structable::Alignment::Left),
+                Align::Right => quote!(::structable::Alignment::Right),
+                Align::Center => quote!(::structable::Alignment::Center),
+            };
+            let field_style = match &field.style {
+                Some(style) => quote!(Some(#style .to_string())),
+                None => quote!(None),
+            };
+            let field_color = match &field.color {
+                Some(color) => quote!(Some(#color .to_string())),
+                None => quote!(None),
+            };
+            let field_unit = match &field.unit {
+                Some(unit) => quote!(Some(#unit .to_string())),
+                None => quote!(None),
+            };
+            let field_max_length = match field.max_length {
+                Some(max_length) => quote!(Some(#max_length)),
+                None => quote!(None),
+            };
+            let field_l10n_key = match &field.l10n_key {
+                Some(key) => quote!(Some(#key .to_string())),
+                None => quote!(None),
+            };
+            let field_description = match field.description() {
+                Some(description) => quote!(Some(#description .to_string())),
+                None => quote!(None),
+            };
+            let field_example = match &field.example {
+                Some(example) => quote!(Some(#example .to_string())),
+                None => quote!(None),
+            };
+            vec_struct_schema.push(quote!(
+                schema.push(::structable::FieldInfo {
+                    title: #field_title .to_string(),
+                    name: #field_name .to_string(),
+                    wide: #field_wide,
+                    verbosity: #field_level,
+                    hide_if_empty: #field_hide_if_empty,
+                    priority: #field_priority,
+                    key: #field_key,
+                    example: #field_example,
+                    description: #field_description,
+                    direction: #field_direction,
+                    l10n_key: #field_l10n_key,
+                    ellipsis: #field_ellipsis,
+                    expand: #field_expand,
+                    align: #field_align,
+                    unit: #field_unit,
+                    max_length: #field_max_length,
+                    tags: vec![#(#field_tags .to_string()),*],
+                    style: ::structable::CellStyle {
+                        style: #field_style,
+                        color: #field_color,
+                    },
+                });
+            ));
+
+            // Save the status fields or status_alt (the one with name `status`) field
+            if field.status {
+                status_fields.push((field, field_title.clone()));
             }
-            _ => quote!(None),
+            if field_title.to_lowercase() == "status" {
+                status_alt_field = Some((field, field_title));
+            }
+        }
+    }
+
+    // Use status_alt if no explicit `status` set. The first status field is the primary one.
+    if status_fields.is_empty() {
+        status_fields.extend(status_alt_field);
+    }
+    let status_field = status_fields.first().map(|(field, _)| *field);
+    let statuses = status_fields
+        .iter()
+        .map(|(field, title)| {
+            let status = status_tokens(Some(field));
+            quote!(
+                if let Some(status) = #status {
+                    statuses.push((#title .to_string(), status));
+                }
+            )
+        })
+        .collect();
+
+    FieldsTokens {
+        headers: vec_struct_headers,
+        schema: vec_struct_schema,
+        rows: vec_struct_fields,
+        columns: vec_struct_columns,
+        status: status_field,
+        statuses,
+        links,
+    }
+}
+
+/// Code of the `status` trait method returning the value of the status `field`
+fn status_tokens(field: Option<&TableStructFieldReceiver>) -> TokenStream {
+    match field {
+        Some(field) => {
+            let field_access = field.accessor();
+            let json_value = field.json_value();
+
+            match (field.optional, field.serialize) {
+                // The custom serialization function only produces the json value
+                (false, _) if field.serialize_with.is_some() => quote!(
+                    Some((#json_value).ok().and_then(|v| serde_json::to_string(&v).ok()).map(|x| x.trim_matches('"').to_string()).unwrap_or_else(|| String::from("<ERROR SERIALIZING>")))
+                ),
+                (true, _) if field.serialize_with.is_some() => quote!(
+                    (#json_value).map(|val| val.ok().and_then(|v| serde_json::to_string(&v).ok()).map(|x| x.trim_matches('"').to_string()).unwrap_or_else(|| String::from("<ERROR SERIALIZING>")))
+                ),
+                (true, false) => quote!(
+                    #field_access .as_ref().map(|val| val.to_string())
+                ),
+                (false, false) => quote!(
+                    Some(#field_access .to_string())
+                ),
+                (true, true) => quote!(
+                    #field_access .as_ref().map(|val| serde_json::to_string(val).map(|x| x.trim_matches('"').to_string()).unwrap_or_else(|_| String::from("<ERROR SERIALIZING>")))
+                ),
+                (false, true) => quote!(
+                    Some(serde_json::to_string(&#field_access ).map(|x| x.trim_matches('"').to_string()).unwrap_or_else(|_| String::from("<ERROR SERIALIZING>")))
+                ),
+            }
+        }
+        _ => quote!(None),
+    }
+}
+
+impl TableStructInputReceiver {
+    /// Code pushing the headers, values and `FieldInfo` of the computed columns
+    fn computed_tokens(&self) -> (Vec<TokenStream>, Vec<TokenStream>, Vec<TokenStream>) {
+        let mut headers = Vec::new();
+        let mut rows = Vec::new();
+        let mut schema = Vec::new();
+        for ComputedColumn {
+            title,
+            method,
+            wide,
+        } in &self.compute
+        {
+            let name = method.to_string();
+            let wide = *wide || self.default_wide;
+            let level = u8::from(wide);
+            let condition = quote!(options.should_return_tagged_column(#title, #name, #level, &[]));
+            headers.push(quote!(
+                if #condition {
+                    headers.push(#title .to_string());
+                }
+            ));
+            rows.push(quote!(
+                if #condition {
+                    row.push(Some(self.#method().to_string()));
+                }
+            ));
+            schema.push(quote!(
+                schema.push(::structable::FieldInfo {
+                    title: #title .to_string(),
+                    name: #name .to_string(),
+                    wide: #wide,
+                    verbosity: #level,
+                    ..Default::default()
+                });
+            ));
+        }
+        (headers, rows, schema)
+    }
+
+    /// Number the fields of the tuple structure
+    fn index_fields(mut self) -> darling::Result<Self> {
+        if let ast::Data::Struct(fields) = &mut self.data {
+            if fields.style == ast::Style::Tuple {
+                for (idx, field) in fields.fields.iter_mut().enumerate() {
+                    field.index = Some(idx);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Generate the `StructTable` implementation of the wrapper delegating to the inner `field`
+    fn newtype_tokens(&self, field: &TableStructFieldReceiver) -> TokenStream {
+        let TableStructInputReceiver {
+            ref ident,
+            ref generics,
+            ref remote,
+            ..
+        } = *self;
+
+        let (imp, ty, wher) = generics.split_for_impl();
+        let target = match remote {
+            Some(path) => quote!(#path),
+            None => quote!(#ident #ty),
         };
+        let inner_ty = &field.ty;
+        let inner = field.accessor();
+        quote! {
+            impl #imp StructTable for #target #wher {
+                fn class_headers<__StructTableOptionsT: StructTableOptions>(options: &__StructTableOptionsT) -> ::std::option::Option<::std::vec::Vec<::std::string::String>> {
+                    <#inner_ty as ::structable::StructTable>::class_headers(options)
+                }
 
-        tokens.extend(quote! {
-            impl #imp StructTable for #ident #ty #wher {
-                fn class_headers<O: StructTableOptions>(options: &O) -> std::option::Option<::std::vec::Vec<::std::string::String>> {
+                fn instance_headers<__StructTableOptionsT: StructTableOptions>(&self, options: &__StructTableOptionsT) -> ::std::option::Option<::std::vec::Vec<::std::string::String>> {
+                    ::structable::StructTable::instance_headers(&#inner, options)
+                }
+
+                fn data<__StructTableOptionsT: StructTableOptions>(&self, options: &__StructTableOptionsT) -> ::std::vec::Vec<::std::option::Option<::std::string::String>> {
+                    ::structable::StructTable::data(&#inner, options)
+                }
+
+                fn status(&self) -> Option<String> {
+                    ::structable::StructTable::status(&#inner)
+                }
+
+                fn statuses(&self) -> ::std::vec::Vec<(::std::string::String, ::std::string::String)> {
+                    ::structable::StructTable::statuses(&#inner)
+                }
+
+                fn links(&self) -> ::std::collections::BTreeMap<::std::string::String, ::std::string::String> {
+                    ::structable::StructTable::links(&#inner)
+                }
+
+                fn data_with_status<__StructTableOptionsT: StructTableOptions>(&self, options: &__StructTableOptionsT) -> (::std::vec::Vec<::std::option::Option<::std::string::String>>, ::std::option::Option<::std::string::String>) {
+                    ::structable::StructTable::data_with_status(&#inner, options)
+                }
+
+                fn schema() -> ::std::vec::Vec<::structable::FieldInfo> {
+                    <#inner_ty as ::structable::StructTable>::schema()
+                }
+            }
+        }
+    }
+
+    /// Generate the `StructTable` implementation of the enum
+    ///
+    /// Unit variants are rendered as their name in the column titled with the enum name, newtype
+    /// variants delegate to the inner `StructTable` and struct variants render their fields.
+    /// Columns depend on the variant, therefore `instance_headers` is always implemented.
+    fn enum_tokens(&self, variants: &[TableEnumVariantReceiver]) -> TokenStream {
+        let TableStructInputReceiver {
+            ref ident,
+            ref generics,
+            dynamic,
+            use_serde_attrs,
+            ref remote,
+            default_wide,
+            ..
+        } = *self;
+
+        let (imp, ty, wher) = generics.split_for_impl();
+        let target = match remote {
+            Some(path) => quote!(#path),
+            None => quote!(#ident #ty),
+        };
+        if variants.is_empty() {
+            return syn::Error::new_spanned(ident, "enums without variants are not supported")
+                .to_compile_error();
+        }
+        let enum_title = ident.to_string();
+        let (computed_headers, computed_rows, computed_schema) = self.computed_tokens();
+
+        let mut header_arms = Vec::new();
+        let mut row_arms = Vec::new();
+        let mut status_arms = Vec::new();
+        let mut statuses_arms = Vec::new();
+        let mut link_arms = Vec::new();
+        let mut enum_schema = Vec::new();
+        let mut unit_schema = false;
+
+        for variant in variants {
+            let variant_ident = &variant.ident;
+            match variant.fields.style {
+                ast::Style::Unit => {
+                    let variant_name = variant_ident.to_string();
+                    let condition = quote!(
+                        options.should_return_column(#enum_title, #enum_title, #default_wide)
+                    );
+                    header_arms.push(quote!(
+                        Self::#variant_ident => {
+                            if #condition {
+                                headers.push(#enum_title .to_string());
+                            }
+                        }
+                    ));
+                    row_arms.push(quote!(
+                        Self::#variant_ident => {
+                            if #condition {
+                                row.push(Some(#variant_name .to_string()));
+                            }
+                        }
+                    ));
+                    status_arms.push(quote!(Self::#variant_ident => None,));
+                    statuses_arms.push(quote!(Self::#variant_ident => {}));
+                    link_arms.push(quote!(Self::#variant_ident => {}));
+                    // All unit variants share the single column
+                    if !unit_schema {
+                        let unit_level = u8::from(default_wide);
+                        unit_schema = true;
+                        enum_schema.push(quote!(
+                            schema.push(::structable::FieldInfo {
+                                title: #enum_title .to_string(),
+                                name: #enum_title .to_string(),
+                                wide: #default_wide,
+                                verbosity: #unit_level,
+                                ..Default::default()
+                            });
+                        ));
+                    }
+                }
+                ast::Style::Tuple if variant.fields.len() == 1 => {
+                    let inner_ty = &variant.fields.fields[0].ty;
+                    header_arms.push(quote!(
+                        Self::#variant_ident(__structable_inner) => {
+                            headers.extend(
+                                <#inner_ty as ::structable::StructTable>::class_headers(options)
+                                    .or_else(|| ::structable::StructTable::instance_headers(__structable_inner, options))
+                                    .unwrap_or_default()
+                            );
+                        }
+                    ));
+                    row_arms.push(quote!(
+                        Self::#variant_ident(__structable_inner) => {
+                            row.extend(::structable::StructTable::data(__structable_inner, options));
+                        }
+                    ));
+                    status_arms.push(quote!(
+                        Self::#variant_ident(__structable_inner) => ::structable::StructTable::status(__structable_inner),
+                    ));
+                    statuses_arms.push(quote!(
+                        Self::#variant_ident(__structable_inner) => {
+                            statuses.extend(::structable::StructTable::statuses(__structable_inner));
+                        }
+                    ));
+                    link_arms.push(quote!(
+                        Self::#variant_ident(__structable_inner) => {
+                            links.extend(::structable::StructTable::links(__structable_inner));
+                        }
+                    ));
+                    enum_schema.push(quote!(
+                        schema.extend(<#inner_ty as ::structable::StructTable>::schema());
+                    ));
+                }
+                ast::Style::Tuple | ast::Style::Struct => {
+                    let FieldsTokens {
+                        headers,
+                        schema,
+                        rows,
+                        status,
+                        statuses,
+                        links,
+                        ..
+                    } = fields_tokens(
+                        variant.fields.iter(),
+                        dynamic,
+                        use_serde_attrs,
+                        default_wide,
+                    );
+                    let bindings = variant.fields.iter().map(|field| {
+                        let binding = &field.binding;
+                        match &field.ident {
+                            Some(field_ident) => quote!(#field_ident: #binding),
+                            None => quote!(#binding),
+                        }
+                    });
+                    let pattern = match variant.fields.style {
+                        ast::Style::Tuple => quote!(Self::#variant_ident(#(#bindings),*)),
+                        _ => quote!(Self::#variant_ident { #(#bindings),* }),
+                    };
+                    let rows = rows.iter().map(|(_, row, _)| row);
+                    let status = status_tokens(status);
+                    header_arms.push(quote!(
+                        #[allow(unused_variables)]
+                        #pattern => {
+                            #(#headers)*
+                        }
+                    ));
+                    row_arms.push(quote!(
+                        #[allow(unused_variables)]
+                        #pattern => {
+                            #(#rows)*
+                        }
+                    ));
+                    status_arms.push(quote!(
+                        #[allow(unused_variables)]
+                        #pattern => #status,
+                    ));
+                    statuses_arms.push(quote!(
+                        #[allow(unused_variables)]
+                        #pattern => {
+                            #(#statuses)*
+                        }
+                    ));
+                    link_arms.push(quote!(
+                        #[allow(unused_variables)]
+                        #pattern => {
+                            #(#links)*
+                        }
+                    ));
+                    enum_schema.extend(schema);
+                }
+            }
+        }
+
+        quote! {
+            impl #imp StructTable for #target #wher {
+                fn instance_headers<__StructTableOptionsT: StructTableOptions>(&self, options: &__StructTableOptionsT) -> ::std::option::Option<::std::vec::Vec<::std::string::String>> {
                     let mut headers: Vec<String> = Vec::new();
-                    #(#vec_struct_headers)*
+                    match self {
+                        #(#header_arms)*
+                    }
+                    #(#computed_headers)*
                     Some(headers)
                 }
 
-                fn data<O: StructTableOptions>(&self, options: &O) -> ::std::vec::Vec<::std::option::Option<::std::string::String>> {
+                fn data<__StructTableOptionsT: StructTableOptions>(&self, options: &__StructTableOptionsT) -> ::std::vec::Vec<::std::option::Option<::std::string::String>> {
                     let mut row: Vec<Option<String>> = Vec::new();
-                    #(#vec_struct_fields)*
+                    match self {
+                        #(#row_arms)*
+                    }
+                    #(#computed_rows)*
                     row
                 }
 
                 fn status(&self) -> Option<String> {
-                    #struct_status
+                    match self {
+                        #(#status_arms)*
+                    }
+                }
+
+                fn statuses(&self) -> ::std::vec::Vec<(::std::string::String, ::std::string::String)> {
+                    let mut statuses = ::std::vec::Vec::new();
+                    match self {
+                        #(#statuses_arms)*
+                    }
+                    statuses
+                }
+
+                fn links(&self) -> ::std::collections::BTreeMap<::std::string::String, ::std::string::String> {
+                    let mut links = ::std::collections::BTreeMap::new();
+                    match self {
+                        #(#link_arms)*
+                    }
+                    links
+                }
+
+                fn schema() -> ::std::vec::Vec<::structable::FieldInfo> {
+                    let mut schema: Vec<::structable::FieldInfo> = Vec::new();
+                    #(#enum_schema)*
+                    #(#computed_schema)*
+                    // Columns shared by the variants are described once
+                    let mut unique: Vec<::structable::FieldInfo> = Vec::with_capacity(schema.len());
+                    for info in schema {
+                        if !unique.iter().any(|x| x.title == info.title) {
+                            unique.push(info);
+                        }
+                    }
+                    unique
+                }
+            }
+        }
+    }
+}
+
+impl ToTokens for TableStructInputReceiver {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let TableStructInputReceiver {
+            ref ident,
+            ref generics,
+            ref data,
+            dynamic,
+            use_serde_attrs,
+            ref remote,
+            default_wide,
+            ..
+        } = *self;
+
+        let (imp, ty, wher) = generics.split_for_impl();
+        let target = match remote {
+            Some(path) => quote!(#path),
+            None => quote!(#ident #ty),
+        };
+        let fields = match data {
+            // Newtype is transparent
+            ast::Data::Struct(fields) if fields.style == ast::Style::Tuple && fields.len() == 1 => {
+                tokens.extend(self.newtype_tokens(&fields.fields[0]));
+                return;
+            }
+            ast::Data::Struct(fields) => fields,
+            ast::Data::Enum(variants) => {
+                tokens.extend(self.enum_tokens(variants));
+                return;
+            }
+        };
+        let mut inner_fields = fields.iter().filter(|f| f.inner);
+        if let Some(inner) = inner_fields.next() {
+            tokens.extend(match inner_fields.next() {
+                None => self.newtype_tokens(inner),
+                Some(other) => {
+                    syn::Error::new_spanned(&other.ty, "only one field can be marked as `inner`")
+                        .to_compile_error()
+                }
+            });
+            return;
+        }
+        let FieldsTokens {
+            headers: mut vec_struct_headers,
+            schema: mut vec_struct_schema,
+            rows: vec_struct_fields,
+            columns: vec_struct_columns,
+            status: status_field,
+            statuses: vec_struct_statuses,
+            links: vec_struct_links,
+        } = fields_tokens(fields.iter(), dynamic, use_serde_attrs, default_wide);
+        // Spread map keys are only known for the instance
+        let dynamic = dynamic || fields.iter().any(|f| f.spread && !f.skip);
+        let struct_status = status_tokens(status_field);
+        let (computed_headers, computed_rows, computed_schema) = self.computed_tokens();
+        vec_struct_headers.extend(computed_headers);
+        vec_struct_schema.extend(computed_schema);
+
+        // Values of all status fields
+        let struct_statuses = match vec_struct_statuses.is_empty() {
+            true => quote!(),
+            false => quote!(
+                fn statuses(&self) -> ::std::vec::Vec<(::std::string::String, ::std::string::String)> {
+                    let mut statuses = ::std::vec::Vec::new();
+                    #(#vec_struct_statuses)*
+                    statuses
+                }
+            ),
+        };
+
+        // Hyperlink targets of the fields with the `link`
+        let struct_links = match vec_struct_links.is_empty() {
+            true => quote!(),
+            false => quote!(
+                fn links(&self) -> ::std::collections::BTreeMap<::std::string::String, ::std::string::String> {
+                    let mut links = ::std::collections::BTreeMap::new();
+                    #(#vec_struct_links)*
+                    links
                 }
+            ),
+        };
+
+        let vec_struct_rows = vec_struct_fields.iter().map(|(_, row, _)| row);
 
+        // Serialize the status field only once when the value is also needed for the column
+        let struct_data_with_status = match status_field {
+            Some(status) if status.serialize => {
+                let json_value = status.json_value();
+                let status_value = match status.optional {
+                    false => quote!(Some(
+                        __structable_status_json
+                            .as_ref()
+                            .ok()
+                            .and_then(|v| serde_json::to_string(v).ok())
+                            .map(|x| x.trim_matches('"').to_string())
+                            .unwrap_or_else(|| String::from("<ERROR SERIALIZING>"))
+                    )),
+                    true => quote!(__structable_status_json.as_ref().map(|val| val
+                        .as_ref()
+                        .ok()
+                        .and_then(|v| serde_json::to_string(v).ok())
+                        .map(|x| x.trim_matches('"').to_string())
+                        .unwrap_or_else(|| String::from("<ERROR SERIALIZING>")))),
+                };
+                let vec_struct_rows_cached =
+                    vec_struct_fields.iter().map(|(field, row, row_cached)| {
+                        match std::ptr::eq(*field, status) {
+                            true => row_cached,
+                            false => row,
+                        }
+                    });
+                quote!(
+                    fn data_with_status<__StructTableOptionsT: StructTableOptions>(&self, options: &__StructTableOptionsT) -> (::std::vec::Vec<::std::option::Option<::std::string::String>>, ::std::option::Option<::std::string::String>) {
+                        let __structable_status_json = #json_value;
+                        let status = #status_value;
+                        let mut row: Vec<Option<String>> = Vec::new();
+                        #(#vec_struct_rows_cached)*
+                        #(#computed_rows)*
+                        (row, status)
+                    }
+                )
             }
+            _ => quote!(),
+        };
 
-            impl #imp StructTable for &#ident #ty #wher {
-                fn class_headers<O: StructTableOptions>(options: &O) -> ::std::option::Option<::std::vec::Vec<::std::string::String>> {
+        // Construct code for the headers trait method
+        let struct_headers = match dynamic {
+            false => quote!(
+                fn class_headers<__StructTableOptionsT: StructTableOptions>(options: &__StructTableOptionsT) -> ::std::option::Option<::std::vec::Vec<::std::string::String>> {
+                    let mut headers: Vec<String> = Vec::new();
+                    #(#vec_struct_headers)*
+                    Some(headers)
+                }
+            ),
+            true => quote!(
+                fn instance_headers<__StructTableOptionsT: StructTableOptions>(&self, options: &__StructTableOptionsT) -> ::std::option::Option<::std::vec::Vec<::std::string::String>> {
                     let mut headers: Vec<String> = Vec::new();
                     #(#vec_struct_headers)*
                     Some(headers)
                 }
+            ),
+        };
+
+        // Indexes of the columns in the unfiltered layout (up to the first flattened field).
+        // Inherent impl is not possible for the remote type.
+        let column_indexes = (0..vec_struct_columns.len()).collect::<Vec<usize>>();
+        let struct_columns = match remote {
+            Some(_) => quote!(),
+            None => quote!(
+                #[allow(dead_code)]
+                impl #imp #target #wher {
+                    #(
+                        /// Index of the column in the row with all fields returned
+                        pub const #vec_struct_columns: usize = #column_indexes;
+                    )*
+                }
+            ),
+        };
+
+        tokens.extend(quote! {
+            #struct_columns
+
+            impl #imp StructTable for #target #wher {
+                #struct_headers
 
-                fn data<O: StructTableOptions>(&self, options: &O) -> ::std::vec::Vec<::std::option::Option<::std::string::String>> {
+                fn data<__StructTableOptionsT: StructTableOptions>(&self, options: &__StructTableOptionsT) -> ::std::vec::Vec<::std::option::Option<::std::string::String>> {
                     let mut row: Vec<Option<String>> = Vec::new();
-                    #(#vec_struct_fields)*
+                    #(#vec_struct_rows)*
+                    #(#computed_rows)*
                     row
                 }
 
@@ -243,6 +1705,18 @@ impl ToTokens for TableStructInputReceiver {
                     #struct_status
                 }
 
+                #struct_data_with_status
+
+                #struct_statuses
+
+                #struct_links
+
+                fn schema() -> ::std::vec::Vec<::structable::FieldInfo> {
+                    let mut schema: Vec<::structable::FieldInfo> = Vec::new();
+                    #(#vec_struct_schema)*
+                    schema
+                }
+
             }
         });
     }
@@ -282,6 +1756,64 @@ mod tests {
         TableStructInputReceiver::from_derive_input(&input).unwrap();
     }
 
+    #[test]
+    fn test_parse_enum() {
+        let input = quote! {
+            #[derive(StructTable)]
+            enum Foo {
+                Bar,
+                Baz(Inner),
+                Qux {
+                    #[structable(title = "r#type")]
+                    r#type: String,
+                },
+            }
+        };
+        let input = syn::parse2(input).unwrap();
+        let receiver = TableStructInputReceiver::from_derive_input(&input).unwrap();
+        let variants = receiver.data.take_enum().unwrap();
+        assert_eq!(
+            variants[2].fields.fields[0].binding,
+            Some(quote::format_ident!("__structable_type"))
+        );
+    }
+
+    #[test]
+    fn test_parse_bool_labels() {
+        let input = quote! {
+            #[derive(StructTable)]
+            struct FooSpec {
+                #[structable(bool_labels = "yes/no/maybe")]
+                foo: bool,
+            }
+        };
+        let input = syn::parse2(input).unwrap();
+        assert!(TableStructInputReceiver::from_derive_input(&input).is_err());
+    }
+
+    #[test]
+    fn test_link_template() {
+        assert_eq!(
+            link_template("https://x/{{{0}}}/{r#type}/{0:>3}?{id}"),
+            (
+                "https://x/{{{__structable_link_0}}}/{__structable_link_1}/{__structable_link_0:>3}?{__structable_link_2}"
+                    .to_string(),
+                vec!["0".to_string(), "type".into(), "id".into()]
+            )
+        );
+
+        let input = quote! {
+            #[derive(StructTable)]
+            struct Pair(u32, #[structable(link = "https://x/{0}")] String);
+        };
+        let input = syn::parse2(input).unwrap();
+        let receiver = TableStructInputReceiver::from_derive_input(&input).unwrap();
+        assert!(!receiver
+            .to_token_stream()
+            .to_string()
+            .contains("compile_error"));
+    }
+
     #[test]
     fn test_parse_wide() {
         let input = quote! {
@@ -308,6 +1840,175 @@ mod tests {
         TableStructInputReceiver::from_derive_input(&input).unwrap();
     }
 
+    #[test]
+    fn test_parse_dynamic() {
+        let input = quote! {
+            #[derive(StructTable)]
+            #[structable(dynamic)]
+            struct FooSpec {
+                #[structable(optional)]
+                foo: Option<String>,
+            }
+        };
+        let input = syn::parse2(input).unwrap();
+        let receiver = TableStructInputReceiver::from_derive_input(&input).unwrap();
+        assert!(receiver.dynamic);
+    }
+
+    #[test]
+    fn test_lifetime_generics() {
+        let input = quote! {
+            #[derive(StructTable)]
+            struct View<'a, 'b: 'a, T: Display + 'b> {
+                name: &'a str,
+                #[structable(optional)]
+                value: Option<&'b T>,
+            }
+        };
+        let input = syn::parse2(input).unwrap();
+        let receiver = TableStructInputReceiver::from_derive_input(&input).unwrap();
+        let tokens = quote!(#receiver).to_string();
+        assert!(tokens.contains(
+            &quote!(impl<'a, 'b: 'a, T: Display + 'b> StructTable for View<'a, 'b, T>).to_string()
+        ));
+    }
+
+    #[test]
+    fn test_collection() {
+        let input = quote! {
+            #[derive(StructTable)]
+            struct FooSpec {
+                a: Vec<String>,
+                b: std::vec::Vec<Option<u32>>,
+                c: Option<Vec<u8>>,
+                d: Option<String>,
+                e: HashMap<String, String>,
+            }
+        };
+        let input = syn::parse2(input).unwrap();
+        let receiver = TableStructInputReceiver::from_derive_input(&input).unwrap();
+        let shapes: Vec<Option<Collection>> = receiver
+            .data
+            .take_struct()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|f| f.collection())
+            .collect();
+        assert_eq!(
+            shapes,
+            vec![
+                Some(Collection::Vec),
+                Some(Collection::VecOfOption),
+                Some(Collection::OptionOfVec),
+                None,
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_column_indexes() {
+        let input = quote! {
+            #[derive(StructTable)]
+            struct FooSpec {
+                id: u32,
+                r#type: String,
+            }
+        };
+        let input = syn::parse2(input).unwrap();
+        let receiver = TableStructInputReceiver::from_derive_input(&input).unwrap();
+        let tokens = quote!(#receiver).to_string();
+        assert!(tokens.contains(
+            &quote!(
+                pub const COL_ID: usize = 0usize;
+            )
+            .to_string()
+        ));
+        assert!(tokens.contains(
+            &quote!(
+                pub const COL_TYPE: usize = 1usize;
+            )
+            .to_string()
+        ));
+    }
+
+    #[test]
+    fn test_const_generics() {
+        let input = quote! {
+            #[derive(StructTable)]
+            struct FooSpec<T: Display, const N: usize = 4> where T: Clone {
+                foo: T,
+            }
+        };
+        let input = syn::parse2(input).unwrap();
+        let receiver = TableStructInputReceiver::from_derive_input(&input).unwrap();
+        let tokens = quote!(#receiver).to_string();
+        assert!(tokens.contains(
+            &quote!(impl<T: Display, const N: usize> StructTable for FooSpec<T, N> where T: Clone)
+                .to_string()
+        ));
+    }
+
+    #[test]
+    fn test_serde_skipped() {
+        let input = quote! {
+            #[derive(StructTable)]
+            #[structable(use_serde_attrs)]
+            struct FooSpec {
+                #[serde(rename = "f", skip_serializing_if = "Option::is_none")]
+                foo: Option<String>,
+                #[serde(default, skip)]
+                bar: String,
+                #[serde(rename(serialize = "b"), skip_serializing)]
+                baz: String,
+            }
+        };
+        let input = syn::parse2(input).unwrap();
+        let receiver = TableStructInputReceiver::from_derive_input(&input).unwrap();
+        assert!(receiver.use_serde_attrs);
+        let skipped: Vec<bool> = receiver
+            .data
+            .take_struct()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|f| f.serde_skipped())
+            .collect();
+        assert_eq!(skipped, vec![false, true, true]);
+    }
+
+    #[test]
+    fn test_remote() {
+        let input = quote! {
+            #[derive(StructTable)]
+            #[structable(remote = "other::Foo")]
+            struct FooDef {
+                foo: String,
+            }
+        };
+        let input = syn::parse2(input).unwrap();
+        let receiver = TableStructInputReceiver::from_derive_input(&input).unwrap();
+        let tokens = quote!(#receiver).to_string();
+        assert!(tokens.contains(&quote!(impl StructTable for other::Foo).to_string()));
+    }
+
+    #[test]
+    fn test_parse_map() {
+        let input = quote! {
+            #[derive(StructTable)]
+            struct FooSpec {
+                #[structable(map(value = "1", to = "enabled"), map(value = "0", to = "disabled"))]
+                foo: u8,
+            }
+        };
+        let input = syn::parse2(input).unwrap();
+        let receiver = TableStructInputReceiver::from_derive_input(&input).unwrap();
+        let fields = receiver.data.take_struct().unwrap().fields;
+        assert_eq!(fields[0].map.len(), 2);
+        assert_eq!(fields[0].map[1].to, "disabled");
+    }
+
     #[test]
     fn test_parse_all_opts() {
         let input = quote! {